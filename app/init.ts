@@ -129,6 +129,10 @@ async function initialize() {
         [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
         program.programId
     );
+    let [reservedIdAddress, reservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(ID)],
+        program.programId
+    );
     //token address for DAO wallet (receives WSOL)
     daoReserveTokenAddress = getAssociatedTokenAddressSync(quoteMint, daoReserveWallet.publicKey)
     try {
@@ -225,20 +229,26 @@ async function initialize() {
         updatesAllowed: updatesAllowed,
         votingEnabledDate: new anchor.BN(votingEnabled),
         launchDate: new anchor.BN(launchDate),
-        runwayFee: runwayFee
+        runwayFee: runwayFee,
+        enforceSolvency: false,
+        mintMode: false
     })
         .accounts({
             creator: provider.wallet.publicKey,
             tokenTrackerBase: tokenTrackerBaseAddress,
             tokenTracker: tokenTrackerAddress,
             tokenState: tokenStateAddress,
+            reservedId: reservedIdAddress,
             baseMint: baseMint,
             baseTokenVault: baseTokenAddress,
             quoteMint: quoteMint,
             quoteReserveTokenAddress: daoReserveTokenAddress,
             quoteSurplusTokenAddress: daoSurplusTokenAddress,
             quoteRunwayTokenAddress: daoRunwayTokenAddress,
+            creatorPaymentToken: null,
+            receiveTokenAccount: null,
             tokenProgram: TOKEN_PROGRAM_ID,
+            baseTokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
             rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         }).rpc()
@@ -312,6 +322,7 @@ async function initialize() {
         tokenState: tokenStateAddress,
         baseTokenVault: baseTokenAddress,
         userBaseToken: baseMintTokenAddr,
+        baseMint: baseMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc()
@@ -35,6 +35,10 @@ async function testBond() {
         [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
         program.programId
     );
+    let [userPositionAddress, userPositionBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("position"), provider.wallet.publicKey.toBuffer()],
+        program.programId
+    );
     //bond amount in lamports
     const bondingAmount = 0.01 * LAMPORTS_PER_SOL
     //bonding period
@@ -50,8 +54,9 @@ async function testBond() {
         provider.wallet.publicKey
     )
     let baseMintTokenAddress = getAssociatedTokenAddressSync(tokenState.baseMintAddress, provider.wallet.publicKey)
+    const couponCount = tokenState.bondCouponCount.addn(1)
     let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-        [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(couponId)],
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
         program.programId
     );
     let voteAccount1Id = "0|DAPE"
@@ -60,16 +65,20 @@ async function testBond() {
         program.programId
     );
 
-    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex).accounts({
+    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex, provider.wallet.publicKey).accounts({
         user: provider.wallet.publicKey,
         tokenTrackerBase: tokenTrackerBaseAddress,
         tokenState: tokenStateAddress,
+        userPosition: userPositionAddress,
         userQuoteToken: quoteMintTokenAddrRes.address,
         quoteRunwayTokenAddress: tokenState.quoteRunwayTokenAddress,
         quoteReserveTokenAddress: tokenState.quoteReserveTokenAddress,
         quoteSurplusTokenAddress: tokenState.quoteSurplusTokenAddress,
+        quoteMint: tokenState.quoteMintAddress,
         coupon: couponAddress,
-        bondVote: bondVoteAddress1,
+        referral: null,
+        referralVault: null,
+        protocolVault: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc({ skipPreflight: true })
@@ -94,8 +103,8 @@ async function testBond() {
     console.log(coupons)
     for (let index = 0; index < coupons.length; index++) {
         const coupon = coupons[index];
-        let couponId = String.fromCharCode(...coupon.account.id).trim()
-        console.log(`State address: ${coupon.account.tokenStateAddress.toBase58()} Redeemer address: ${coupon.account.redeemerAddress.toBase58()} Id: ${couponId}`)
+        let couponLabel = String.fromCharCode(...coupon.account.label).trim()
+        console.log(`State address: ${coupon.account.tokenStateAddress.toBase58()} Redeemer address: ${coupon.account.redeemerAddress.toBase58()} Label: ${couponLabel}`)
         let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
             [Buffer.from(TRACKER_ID)],
             program.programId
@@ -105,26 +114,28 @@ async function testBond() {
             program.programId
         );
         let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-            [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(String.fromCharCode(...coupon.account.id).trim())],
+            [tokenStateAddress.toBuffer(), Buffer.from("coupon"), coupon.account.couponCount.toArrayLike(Buffer, "le", 8)],
             program.programId
         );
         let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
             [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
             program.programId
         );
-        let tx = await program.methods.redeem(couponId).accounts({
+        let tx = await program.methods.redeem().accounts({
             user: provider.wallet.publicKey,
             tokenTrackerBase: tokenTrackerBaseAddress,
             tokenState: tokenStateAddress,
+            userPosition: userPositionAddress,
             userBaseToken: baseMintTokenAddress,
             baseTokenVault: baseTokenAddress,
+            baseMint: tokenState.baseMintAddress,
             coupon: couponAddress,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
         }).rpc()
         console.log("Claim tx", tx)
-        const couponRes = await program.account.bondCoupon.fetch(couponAddress)
-        console.log("Coupon claimed:", couponRes.isRedeemed)
+        const couponRes = await provider.connection.getAccountInfo(couponAddress)
+        console.log("Coupon closed:", couponRes == null)
     }
 
 }
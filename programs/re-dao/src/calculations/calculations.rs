@@ -33,29 +33,31 @@ pub fn surplus(quote_bonded: u64, reserve: u64) -> Result<u64> {
     Ok(eq_final)
 }
 
-pub fn reserve(floor_price: u64, total_emissions: u64, quote_decimals: u8) -> Result<u64> {
-    //quote bonded / mps
-    //TODO as floor price is leveled up, have to leve it down after eq
-    let eq_0 = floor_price.checked_mul(total_emissions).or_arith_error()?;
-
-    let base = 10 as u64;
-    let base_pow = base.checked_pow(quote_decimals.into()).or_arith_error()?;
+pub fn reserve(floor_price: u64, total_emissions: u64, base_decimals: u8) -> Result<u64> {
+    //(floor price * total emissions) / 10^base_decimals
+    //floor price is quote atoms per whole base token, so scale the base atoms back down
+    let base = 10_u128;
+    let base_pow = base.checked_pow(base_decimals.into()).or_arith_error()?;
+    let eq_0 = (floor_price as u128).checked_mul(total_emissions.into()).or_arith_error()?;
     let eq_final = eq_0.checked_div(base_pow).or_arith_error()?;
-    Ok(eq_final)
+    u64::try_from(eq_final).ok().or_arith_error()
 }
 
-pub fn floor_price(quote_bonded: u64, mps: u64, quote_decimals: u8) -> Result<u64> {
-    //quote bonded / mps
-    //TODO - have to multiply floor pool by 10^number of decimals?
-    let base = 10 as u64;
-    let base_pow = base.checked_pow(quote_decimals.into()).or_arith_error()?;
-    let quote_bonded_n = quote_bonded.checked_mul(base_pow).or_arith_error()?;
-    let eq_final = quote_bonded_n.checked_div(mps).or_arith_error()?;
-    //TODO subtract downbards 1 point?
-    Ok(eq_final)
+pub fn floor_price(quote_bonded: u64, mps: u64, base_decimals: u8) -> Result<u64> {
+    //(quote bonded * 10^base_decimals) / mps
+    //quote atoms per whole base token, independent of the quote decimals
+    if mps == 0 {
+        return Ok(0);
+    }
+    let base = 10_u128;
+    let base_pow = base.checked_pow(base_decimals.into()).or_arith_error()?;
+    let quote_bonded_n = (quote_bonded as u128).checked_mul(base_pow).or_arith_error()?;
+    let eq_final = quote_bonded_n.checked_div(mps.into()).or_arith_error()?;
+    u64::try_from(eq_final).ok().or_arith_error()
 }
 
 pub fn bond_reward(amount: u64, cost: u64, emissions: u64, multiplier: u64, bps: u32) -> Result<u64> {
+    //amount and cost are quote atoms, emissions are base atoms, so decimals cancel out
     //(((amount / cost) * emissions) * multiplier) / bps
     //amount * emissions -
     let eq_0: u128 = (amount as u128).checked_mul(emissions.into()).or_arith_error()?;
@@ -89,4 +91,111 @@ pub fn fee(amount: u64, fee: u64, max_bps: u64) -> Result<u64> {
         .checked_div(max_bps)
         .or_arith_error()?;
     Ok(res)
-}
\ No newline at end of file
+}
+
+pub fn fees_exceed_total(runway_fee: u32, fee_bps: u32, protocol_fee: u32, protocol_bps: u32) -> Result<bool> {
+    //(runway_fee / fee_bps) + (protocol_fee / protocol_bps) > 1, cross multiplied
+    let eq_0 = (runway_fee as u128).checked_mul(protocol_bps.into()).or_arith_error()?;
+    let eq_1 = (protocol_fee as u128).checked_mul(fee_bps.into()).or_arith_error()?;
+    let eq_2 = (fee_bps as u128).checked_mul(protocol_bps.into()).or_arith_error()?;
+    Ok(eq_0.checked_add(eq_1).or_arith_error()? > eq_2)
+}
+
+pub fn max_period_length(period_lengths: &[i64; 10], period_enabled: &[bool; 10]) -> i64 {
+    //longest enabled bonding period, used as the full ve lock
+    period_lengths
+        .iter()
+        .zip(period_enabled.iter())
+        .filter(|(_, &enabled)| enabled)
+        .map(|(&length, _)| length)
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn voting_power(tokens: u64, redemption_date: i64, now: i64, max_lock: i64) -> Result<u64> {
+    //(tokens * time left) / max lock, decays linearly to 0 at maturity
+    if redemption_date <= now || max_lock <= 0 {
+        return Ok(0);
+    }
+    let time_left = redemption_date.checked_sub(now).or_arith_error()?.min(max_lock);
+    let eq_0 = (tokens as u128).checked_mul(time_left as u128).or_arith_error()?;
+    let eq_final = eq_0.checked_div(max_lock as u128).or_arith_error()?;
+    u64::try_from(eq_final).ok().or_arith_error()
+}
+
+pub fn creation_cost(id_length: usize, cost: u64, vanity_costs: &[u64; 5], reserved_price: Option<u64>) -> u64 {
+    //reserved ids have their own price, short ids are priced by length, everything else pays the base cost
+    if let Some(price) = reserved_price {
+        return price;
+    }
+    match id_length.checked_sub(1).and_then(|index| vanity_costs.get(index)) {
+        Some(&vanity_cost) if vanity_cost > 0 => vanity_cost,
+        _ => cost,
+    }
+}
+
+pub fn solvency_ratio(total_topup: u64, total_redeemed: u64, outstanding_liabilities: u64, max_bps: u64) -> Result<u64> {
+    //((total_topup - total_redeemed) * max_bps) / outstanding liabilities
+    if outstanding_liabilities == 0 {
+        return Ok(u64::MAX);
+    }
+    let available = total_topup.saturating_sub(total_redeemed);
+    let eq_0 = (available as u128).checked_mul(max_bps.into()).or_arith_error()?;
+    let eq_final = eq_0.checked_div(outstanding_liabilities.into()).or_arith_error()?;
+    Ok(eq_final.try_into().unwrap_or(u64::MAX))
+}
+
+pub fn vested_amount(total_amount: u64, start_date: i64, cliff_date: i64, end_date: i64, now: i64) -> Result<u64> {
+    //nothing before the cliff, then (total * time elapsed) / vesting length until fully vested
+    if now < cliff_date {
+        return Ok(0);
+    }
+    if now >= end_date {
+        return Ok(total_amount);
+    }
+    let elapsed = now.checked_sub(start_date).or_arith_error()?;
+    let length = end_date.checked_sub(start_date).or_arith_error()?;
+    let eq_0 = (total_amount as u128).checked_mul(elapsed as u128).or_arith_error()?;
+    let eq_final = eq_0.checked_div(length as u128).or_arith_error()?;
+    u64::try_from(eq_final).ok().or_arith_error()
+}
+
+pub fn early_exit_penalty(tokens: u64, penalty: u32, bond_date: i64, redemption_date: i64, now: i64, max_bps: u32) -> Result<u64> {
+    //(tokens * penalty * time left) / (lock length * max_bps), decays linearly to 0 at maturity
+    if redemption_date <= now || redemption_date <= bond_date {
+        return Ok(0);
+    }
+    let length = redemption_date.checked_sub(bond_date).or_arith_error()?;
+    let time_left = redemption_date.checked_sub(now).or_arith_error()?.min(length);
+    let eq_0 = (tokens as u128)
+        .checked_mul(penalty.into()).or_arith_error()?
+        .checked_mul(time_left as u128).or_arith_error()?;
+    let eq_1 = (length as u128).checked_mul(max_bps.into()).or_arith_error()?;
+    let eq_final = eq_0.checked_div(eq_1).or_arith_error()?;
+    u64::try_from(eq_final).ok().or_arith_error()
+}
+
+pub fn vesting_cliff(start_date: i64, end_date: i64, cliff_fraction: u32, max_bps: u32) -> Result<i64> {
+    //start + (length * cliff fraction) / max_bps
+    let length = end_date.checked_sub(start_date).or_arith_error()?;
+    let eq_0 = (length.max(0) as u128).checked_mul(cliff_fraction.into()).or_arith_error()?;
+    let eq_1 = eq_0.checked_div(max_bps.into()).or_arith_error()?;
+    let eq_1 = i64::try_from(eq_1).ok().or_arith_error()?;
+    let eq_final = start_date.checked_add(eq_1).or_arith_error()?;
+    Ok(eq_final)
+}
+
+pub fn rollover_bonus(principal: u64, multiplier: u32, bps: u32, period_length: i64, max_lock: i64) -> Result<u64> {
+    //(principal * (multiplier - bps) * period length) / (bps * max lock)
+    //the full bonus is only paid for the longest period, so short rollovers can't compound it
+    if period_length <= 0 || max_lock <= 0 {
+        return Ok(0);
+    }
+    let bonus_bps = (multiplier as u128).checked_sub(bps.into()).or_arith_error()?;
+    let eq_0 = (principal as u128)
+        .checked_mul(bonus_bps).or_arith_error()?
+        .checked_mul(period_length.min(max_lock) as u128).or_arith_error()?;
+    let eq_1 = (bps as u128).checked_mul(max_lock as u128).or_arith_error()?;
+    let eq_final = eq_0.checked_div(eq_1).or_arith_error()?;
+    u64::try_from(eq_final).ok().or_arith_error()
+}
@@ -1 +1,17 @@
-pub const AUTH: &str = "4ZNzRbHZahJDK9QkeAcN1tipqgKbmfBweZn27SWpVrvW";
+use anchor_lang::prelude::Pubkey;
+
+//4ZNzRbHZahJDK9QkeAcN1tipqgKbmfBweZn27SWpVrvW
+//bootstraps new token tracker bases, each base is then administered by its own auth_wallet
+pub const AUTH: Pubkey = Pubkey::new_from_array([
+    52, 222, 130, 203, 157, 93, 145, 196, 64, 71, 19, 197, 102, 175, 57, 90,
+    218, 100, 32, 125, 130, 73, 124, 36, 8, 140, 182, 12, 245, 252, 251, 179,
+]);
+
+//protocol fees are out of their own denominator, token states can use a different fee_bps
+pub const PROTOCOL_FEE_BPS: u32 = 100000;
+
+//protocol fee cap, out of PROTOCOL_FEE_BPS (5%)
+pub const MAX_PROTOCOL_FEE_BPS: u32 = 5000;
+
+//rollover multiplier cap, out of the token state reward_bps of 10000 (2x)
+pub const MAX_ROLLOVER_MULTIPLIER: u32 = 20000;
@@ -15,6 +15,11 @@ impl OrArithError<u64> for Option<u64> {
         self.ok_or(error!(CustomErrorCode::ArithmeticError))
     }
 }
+impl OrArithError<i64> for Option<i64> {
+    fn or_arith_error(self) -> StdResult<i64, Error> {
+        self.ok_or(error!(CustomErrorCode::ArithmeticError))
+    }
+}
 impl OrArithError<u8> for Option<u8> {
     fn or_arith_error(self) -> StdResult<u8, Error> {
         self.ok_or(error!(CustomErrorCode::ArithmeticError))
@@ -31,16 +36,24 @@ pub enum CustomErrorCode {
     ReserveDeltaMismatchError,
     #[msg("Runway fee can't exceed 100%.")]
     RunwayFeeError,
+    #[msg("Base vault can't cover this bond.")]
+    InsolventVaultError,
     #[msg("Period is disabled.")]
     DisabledPeriodError,
     #[msg("Amount must not be zero.")]
     AmountIsZeroError,
     #[msg("Initial reserve too large!")]
     InitialReserveTooLargeError,
+    #[msg("Token state must be the base mint authority!")]
+    InvalidMintAuthority,
     #[msg("Base and quote address match!")]
     BaseAndQuoteMatch,
     #[msg("Invalid creator!")]
     InvalidCreator,
+    #[msg("Creation payment accounts missing!")]
+    MissingPaymentAccountError,
+    #[msg("Token is delisted!")]
+    DelistedError,
     #[msg("Invalid id length!")]
     InvalidIdLength,
     #[msg("Period lengths must be 10")]
@@ -51,6 +64,50 @@ pub enum CustomErrorCode {
     PeriodTreasurySplitError,
     #[msg("Period enabled must be 10")]
     PeriodEnabledError,
+    #[msg("Voting is closed.")]
+    VotingClosedError,
+    #[msg("Signer can't vote for this owner!")]
+    InvalidVoter,
+    #[msg("Invalid coupon!")]
+    InvalidCouponError,
+    #[msg("No voting power.")]
+    NoVotingPowerError,
+    #[msg("Allocation exceeds the initial reserve!")]
+    AllocationExceedsReserveError,
+    #[msg("Invalid vesting schedule!")]
+    InvalidVestingScheduleError,
+    #[msg("Nothing vested to claim.")]
+    NothingToClaimError,
+    #[msg("Mint extension is not supported!")]
+    UnsupportedMintExtensionError,
+    #[msg("Quote token account is required unless bonding native SOL!")]
+    MissingQuoteTokenError,
+    #[msg("Referral share can't exceed 100% of the runway fee.")]
+    ReferralFeeError,
+    #[msg("Can't refer yourself!")]
+    InvalidReferrerError,
+    #[msg("Referral vault is required with a referrer!")]
+    MissingReferralVaultError,
+    #[msg("Protocol fee is above the maximum!")]
+    ProtocolFeeError,
+    #[msg("Protocol vault is required while a protocol fee is set!")]
+    MissingProtocolVaultError,
+    #[msg("Early exit is not enabled!")]
+    EarlyExitDisabledError,
+    #[msg("Early exit penalty can't exceed 100%.")]
+    EarlyExitPenaltyError,
+    #[msg("Rollovers are not enabled!")]
+    RolloverDisabledError,
+    #[msg("Rollover multiplier must be between 1x and 2x.")]
+    RolloverMultiplierError,
+    #[msg("Cancellations are not enabled!")]
+    CancelDisabledError,
+    #[msg("Cancellation window has passed.")]
+    CancelWindowError,
+    #[msg("Token state must own or be delegated the reserve and surplus accounts to refund cancellations!")]
+    CancelAuthorityError,
+    #[msg("Runway and protocol fees can't exceed 100% combined.")]
+    TotalFeeError,
     #[msg("Zero Error")]
     ZeroError,
     #[msg("Arithmetic Error")]
@@ -0,0 +1,94 @@
+use crate::calculations::calculations;
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, Allocation};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimAllocation<'info> {
+    #[account(address = allocation.beneficiary_address)]
+    pub beneficiary: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"allocation".as_ref(), beneficiary.key().as_ref()],
+        bump = allocation.allocation_bump,
+    )]
+    pub allocation: Box<Account<'info, Allocation>>,
+    #[account(
+        mut,
+        constraint = beneficiary_base_token.mint == token_state.base_mint_address,
+        constraint = &beneficiary_base_token.owner == beneficiary.to_account_info().key
+    )]
+    pub beneficiary_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
+        bump = token_state.base_token_vault_bump
+    )]
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = token_state.base_mint_address
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.base_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle(ctx: Context<ClaimAllocation>) -> Result<()> {
+    let base = &ctx.accounts.token_tracker_base;
+    let token_state = &mut ctx.accounts.token_state;
+    let allocation = &mut ctx.accounts.allocation;
+
+    let clock = Clock::get()?;
+    let vested = calculations::vested_amount(
+        allocation.total_amount,
+        allocation.start_date,
+        allocation.cliff_date,
+        allocation.end_date,
+        clock.unix_timestamp,
+    )?;
+    let claimable = vested.checked_sub(allocation.claimed_amount).or_arith_error()?;
+    if claimable == 0 {
+        return Err(error!(CustomErrorCode::NothingToClaimError));
+    }
+
+    let id = token_state.id.as_ref();
+    let base_key = base.key();
+    let seeds = &[
+        base_key.as_ref(),
+        id.trim_ascii_whitespace(),
+        &[token_state.token_state_bump],
+    ];
+    transfers::release_base_with_signer(
+        token_state.mint_mode,
+        token_state.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.base_token_vault.to_account_info(),
+        ctx.accounts.beneficiary_base_token.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        claimable,
+        token_state.base_decimals,
+        seeds,
+    )?;
+    allocation.claimed_amount = vested;
+
+    //vault payouts count towards total_redeemed so the solvency ratio stays accurate
+    token_state.total_redeemed = token_state.total_redeemed.checked_add(claimable).or_arith_error()?;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_sub(claimable).or_arith_error()?;
+    Ok(())
+}
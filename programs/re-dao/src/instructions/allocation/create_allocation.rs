@@ -0,0 +1,69 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, Allocation};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct CreateAllocation<'info> {
+    #[account(
+        mut,
+        address = token_state.creator_address
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        init,
+        seeds = [token_state.key().as_ref(), b"allocation".as_ref(), beneficiary.as_ref()],
+        bump,
+        payer = creator,
+        space=150
+    )]
+    pub allocation: Box<Account<'info, Allocation>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<CreateAllocation>, beneficiary: Pubkey, amount: u64, start_date: i64, cliff_length: i64, vesting_length: i64) -> Result<()> {
+    if amount == 0 {
+        return Err(error!(CustomErrorCode::AmountIsZeroError));
+    }
+    if cliff_length < 0 || vesting_length < cliff_length {
+        return Err(error!(CustomErrorCode::InvalidVestingScheduleError));
+    }
+    let token_state = &mut ctx.accounts.token_state;
+
+    //allocations can't hand out more than the initial reserve
+    let allocated_reserve = token_state.allocated_reserve.checked_add(amount).or_arith_error()?;
+    if allocated_reserve > token_state.initial_reserve {
+        return Err(error!(CustomErrorCode::AllocationExceedsReserveError));
+    }
+    token_state.allocated_reserve = allocated_reserve;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_add(amount).or_arith_error()?;
+
+    let allocation = &mut ctx.accounts.allocation;
+    allocation.token_state_address = token_state.key();
+    allocation.beneficiary_address = beneficiary;
+    allocation.total_amount = amount;
+    allocation.claimed_amount = 0;
+    allocation.start_date = start_date;
+    allocation.cliff_date = start_date.checked_add(cliff_length).or_arith_error()?;
+    allocation.end_date = start_date.checked_add(vesting_length).or_arith_error()?;
+    allocation.allocation_bump = *ctx.bumps.get("allocation").unwrap();
+    Ok(())
+}
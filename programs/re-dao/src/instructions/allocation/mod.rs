@@ -0,0 +1,5 @@
+pub mod create_allocation;
+pub mod claim_allocation;
+
+pub use create_allocation::*;
+pub use claim_allocation::*;
@@ -1,20 +1,23 @@
 use crate::calculations::calculations::{total_emissions_at_epoch, epoch_emissions, epoch_emission_rate};
+use crate::constants::PROTOCOL_FEE_BPS;
 use crate::errors::{CustomErrorCode, OrArithError};
-use crate::structs::{BondVote, TokenState, TokenTrackerBase, BondCoupon};
+use crate::structs::{TokenState, TokenTrackerBase, BondCoupon, Referral, UserPosition};
 use crate::utils::ascii_trim::TrimAsciiWhitespace;
+use crate::utils::token_extensions::{is_native_mint, transfer_fee};
 use crate::{
-    calculations::calculations::{bond_amount, bond_reward, fee, floor_price, reserve, surplus},
+    calculations::calculations::{bond_amount, bond_reward, fee, fees_exceed_total, floor_price, reserve, surplus, vesting_cliff},
     transfers::transfers,
 };
 use anchor_lang::solana_program::vote;
 use anchor_lang::{prelude::*, solana_program::system_program};
-use anchor_spl::token::{self, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_lang::prelude::Clock;
 
 //TODO, store how much bonded and emitted through each period value!
 #[derive(Accounts)]
-#[instruction(id: String)]
+#[instruction(label: Option<String>, amount: u64, period_index: u8, beneficiary: Pubkey)]
 pub struct Bond<'info> {
+    //pays quote and rent, the beneficiary gets the coupon
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
@@ -33,59 +36,102 @@ pub struct Bond<'info> {
         constraint = &user_quote_token.mint == &token_state.quote_mint_address,
         constraint = &user_quote_token.owner == user.to_account_info().key
     )]
-    pub user_quote_token: Box<Account<'info, TokenAccount>>,
+    //left out when bonding native SOL, lamports are taken from the user instead
+    pub user_quote_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         mut,
         constraint = &quote_runway_token_address.mint == &token_state.quote_mint_address,
         address = token_state.quote_runway_token_address,
     )]
-    pub quote_runway_token_address: Box<Account<'info, TokenAccount>>,
+    pub quote_runway_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         constraint = &quote_reserve_token_address.mint == &token_state.quote_mint_address,
         address = token_state.quote_reserve_token_address,
     )]
-    pub quote_reserve_token_address: Box<Account<'info, TokenAccount>>,
+    pub quote_reserve_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         constraint = &quote_surplus_token_address.mint == &token_state.quote_mint_address,
         address = token_state.quote_surplus_token_address,
     )]
-    pub quote_surplus_token_address: Box<Account<'info, TokenAccount>>,
+    pub quote_surplus_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = token_state.quote_mint_address)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    //keyed by the coupon count so the coupon can still be found after a transfer
     #[account(
         init,
-        seeds = [token_state.key().as_ref(), user.key().as_ref(), id.as_bytes()],
+        seeds = [
+            token_state.key().as_ref(),
+            b"coupon".as_ref(),
+            token_state.bond_coupon_count
+            .checked_add(1)
+            .or_arith_error()?
+            .to_le_bytes().as_ref()],
         bump,
         payer = user,
         space=420
     )]
     pub coupon: Box<Account<'info, BondCoupon>>,
+    //created with the owner's first coupon
+    #[account(
+        init_if_needed,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), beneficiary.as_ref()],
+        bump,
+        payer = user,
+        space=150
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    //optional referrer, earns referral_bps of the runway fee. neither the payer nor the beneficiary can refer themselves
     #[account(
         mut,
-        constraint = &bond_vote.token_state_address == token_state.to_account_info().key
+        constraint = referral.token_state_address == token_state.key(),
+        constraint = referral.referrer_address != user.key() @ CustomErrorCode::InvalidReferrerError,
+        constraint = referral.referrer_address != beneficiary @ CustomErrorCode::InvalidReferrerError
+    )]
+    pub referral: Option<Box<Account<'info, Referral>>>,
+    #[account(
+        mut,
+        address = token_state.referral_vault_address
+    )]
+    pub referral_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    //only needed while the tracker charges a protocol fee
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), b"protocol_vault".as_ref(), token_state.quote_mint_address.as_ref()],
+        bump,
     )]
-    pub bond_vote: Option<Account<'info, BondVote>>,
-    pub token_program: Program<'info, Token>,
+    pub protocol_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(address = token_state.quote_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
-pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) -> Result<()> {
+pub fn handle(ctx: Context<Bond>, label: Option<String>, amount: u64, period_index: u8, beneficiary: Pubkey) -> Result<()> {
     //todo, min re out, could end up buying at next epoch accidentaly? specify epoch, epochs must == as a safety check
     //todo min amount should also cover runway fee too
     //TODO what if  amount to next halving is less than minimum amount? it is impossible to move forward? add min bond amount
     //todo check if pool is launched
-    let id_bytes = id.as_bytes();
-    if id_bytes.len() > 10 {
+    //the label is only for display, coupons are keyed by the coupon count
+    let label = label.unwrap_or_default();
+    let label_bytes = label.as_bytes();
+    if label_bytes.len() > 10 {
         return Err(error!(CustomErrorCode::InvalidIdLength));
     }
-    let mut id_data = [b' '; 10];
-    id_data[..id_bytes.len()].copy_from_slice(id_bytes);
+    let mut label_data = [b' '; 10];
+    label_data[..label_bytes.len()].copy_from_slice(label_bytes);
 
     if amount == 0 {
         return Err(error!(CustomErrorCode::ZeroError));
     }
     let token_state = &mut ctx.accounts.token_state;
+    if token_state.delisted {
+        return Err(error!(CustomErrorCode::DelistedError));
+    }
+    if ctx.accounts.user_quote_token.is_none() && !is_native_mint(&token_state.quote_mint_address) {
+        return Err(error!(CustomErrorCode::MissingQuoteTokenError));
+    }
     //check if period_index is < 10
     if period_index as usize >= token_state.period_enabled.len() {
         return Err(error!(CustomErrorCode::PeriodLengthError));
@@ -147,6 +193,18 @@ pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) ->
         // );
     }
 
+    //protocol fee for the factory, skimmed alongside the runway fee
+    let protocol_fee_bps = ctx.accounts.token_tracker_base.protocol_fee_bps;
+    if fees_exceed_total(token_state.runway_fee, token_state.fee_bps, protocol_fee_bps, PROTOCOL_FEE_BPS)? {
+        return Err(error!(CustomErrorCode::TotalFeeError));
+    }
+    let protocol_fee_amount = fee(
+        amount_mut,
+        protocol_fee_bps.into(),
+        PROTOCOL_FEE_BPS.into(),
+    )?;
+    amount_post_fee = amount_post_fee.checked_sub(protocol_fee_amount).or_arith_error()?;
+
     // mps
     let last_true_index = token_state
         .period_enabled
@@ -179,6 +237,21 @@ pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) ->
         //amount is left out
     }
 
+    //base vault has to cover every coupon issued so far
+    let new_outstanding_liabilities = token_state
+        .outstanding_liabilities
+        .checked_add(reward)
+        .or_arith_error()?;
+    //mint mode is always solvent, coupons are minted on redemption
+    if token_state.enforce_solvency && !token_state.mint_mode {
+        //payouts can exceed topups when the vault is funded some other way
+        let available = token_state.total_topup.saturating_sub(token_state.total_redeemed);
+        if new_outstanding_liabilities > available {
+            return Err(error!(CustomErrorCode::InsolventVaultError));
+        }
+    }
+    token_state.outstanding_liabilities = new_outstanding_liabilities;
+
     token_state.total_emissions = new_total_emissions;
     token_state.mps = new_total_maximum_emissions;
 
@@ -187,11 +260,6 @@ pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) ->
         .quote_bonded
         .checked_add(amount_post_fee)
         .or_arith_error()?;
-    //add to runway fee
-    token_state.total_runway_reserve = token_state
-        .total_runway_reserve
-        .checked_add(runway_fee_amount)
-        .or_arith_error()?;
     msg!(
         ":={},{}, {}",
         token_state.quote_bonded,
@@ -220,41 +288,111 @@ pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) ->
         growth_pool_amount,
         base_pool_amount
     );
-    //token_state.floor_price = new_floor_price;
-    token_state.total_reserve = token_state.total_reserve.checked_add(base_pool_amount).or_arith_error()?;
-    token_state.total_surplus_reserve = token_state.total_surplus_reserve.checked_add(growth_pool_amount).or_arith_error()?;
+    token_state.floor_price = floor_price(token_state.quote_bonded, token_state.mps, token_state.base_decimals)?;
+    //referrer's cut comes out of the runway fee
+    let referral_amount = match &ctx.accounts.referral {
+        Some(_) => fee(
+            runway_fee_amount,
+            token_state.referral_bps.into(),
+            token_state.fee_bps.into(),
+        )?,
+        None => 0,
+    };
+    let runway_amount = runway_fee_amount.checked_sub(referral_amount).or_arith_error()?;
+    //credit what the vaults actually receive, token-2022 transfer fees are withheld on the way in
+    let quote_mint_info = ctx.accounts.quote_mint.to_account_info();
+    let runway_received = runway_amount.checked_sub(transfer_fee(&quote_mint_info, runway_amount)?).or_arith_error()?;
+    let base_pool_received = base_pool_amount.checked_sub(transfer_fee(&quote_mint_info, base_pool_amount)?).or_arith_error()?;
+    let growth_pool_received = growth_pool_amount.checked_sub(transfer_fee(&quote_mint_info, growth_pool_amount)?).or_arith_error()?;
+    token_state.total_runway_reserve = token_state.total_runway_reserve.checked_add(runway_received).or_arith_error()?;
+    token_state.total_reserve = token_state.total_reserve.checked_add(base_pool_received).or_arith_error()?;
+    token_state.total_surplus_reserve = token_state.total_surplus_reserve.checked_add(growth_pool_received).or_arith_error()?;
     // send quote tokens to reserve, surplus, runway
-    if runway_fee_amount > 0 {
+    if runway_amount > 0 {
         //runway transfer
-        transfers::transfer(
+        transfers::transfer_quote(
             ctx.accounts.user.to_account_info(),
-            ctx.accounts.user_quote_token.to_account_info(),
+            ctx.accounts.user_quote_token.as_ref().map(|token| token.to_account_info()),
+            ctx.accounts.quote_mint.to_account_info(),
             ctx.accounts.quote_runway_token_address.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
-            runway_fee_amount,
+            ctx.accounts.system_program.to_account_info(),
+            runway_amount,
+            token_state.quote_decimals,
+        )?;
+    }
+
+    if protocol_fee_amount > 0 {
+        //protocol transfer, kept apart from the runway reserve
+        let protocol_vault = ctx
+            .accounts
+            .protocol_vault
+            .as_ref()
+            .ok_or(CustomErrorCode::MissingProtocolVaultError)?;
+        let protocol_received = protocol_fee_amount.checked_sub(transfer_fee(&quote_mint_info, protocol_fee_amount)?).or_arith_error()?;
+        transfers::transfer_quote(
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.user_quote_token.as_ref().map(|token| token.to_account_info()),
+            ctx.accounts.quote_mint.to_account_info(),
+            protocol_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            protocol_fee_amount,
+            token_state.quote_decimals,
         )?;
+        token_state.total_protocol_fees = token_state.total_protocol_fees.checked_add(protocol_received).or_arith_error()?;
+    }
+
+    if referral_amount > 0 {
+        //referral transfer, held in the referral vault until claimed
+        let referral_vault = ctx
+            .accounts
+            .referral_vault
+            .as_ref()
+            .ok_or(CustomErrorCode::MissingReferralVaultError)?;
+        let referral_received = referral_amount.checked_sub(transfer_fee(&quote_mint_info, referral_amount)?).or_arith_error()?;
+        transfers::transfer_quote(
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.user_quote_token.as_ref().map(|token| token.to_account_info()),
+            ctx.accounts.quote_mint.to_account_info(),
+            referral_vault.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            referral_amount,
+            token_state.quote_decimals,
+        )?;
+        token_state.total_referral_fees = token_state.total_referral_fees.checked_add(referral_received).or_arith_error()?;
+        if let Some(referral) = &mut ctx.accounts.referral {
+            referral.total_earned = referral.total_earned.checked_add(referral_received).or_arith_error()?;
+        }
     }
     
     
     if base_pool_amount > 0 {
         //reserve transfer
-        transfers::transfer(
+        transfers::transfer_quote(
             ctx.accounts.user.to_account_info(),
-            ctx.accounts.user_quote_token.to_account_info(),
+            ctx.accounts.user_quote_token.as_ref().map(|token| token.to_account_info()),
+            ctx.accounts.quote_mint.to_account_info(),
             ctx.accounts.quote_reserve_token_address.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
             base_pool_amount,
+            token_state.quote_decimals,
         )?;
     }
 
     if growth_pool_amount > 0 {
         //surplus transfer
-        transfers::transfer(
+        transfers::transfer_quote(
             ctx.accounts.user.to_account_info(),
-            ctx.accounts.user_quote_token.to_account_info(),
+            ctx.accounts.user_quote_token.as_ref().map(|token| token.to_account_info()),
+            ctx.accounts.quote_mint.to_account_info(),
             ctx.accounts.quote_surplus_token_address.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
             growth_pool_amount,
+            token_state.quote_decimals,
         )?;
     }
 
@@ -265,12 +403,36 @@ pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) ->
     coupon.coupon_count = token_state.bond_coupon_count;
     coupon.period_index = period_index;
     coupon.token_state_address = token_state.key();
-    coupon.redeemer_address = ctx.accounts.user.key();
+    coupon.redeemer_address = beneficiary;
     let clock = Clock::get()?;
     coupon.redemption_date = clock.unix_timestamp.checked_add(period_length).unwrap();
+    coupon.bond_date = clock.unix_timestamp;
+    coupon.vesting_linear = token_state.period_vesting_linear[period_index as usize];
+    coupon.cliff_date = if coupon.vesting_linear {
+        vesting_cliff(coupon.bond_date, coupon.redemption_date, token_state.period_cliff_fraction[period_index as usize], token_state.fee_bps)?
+    } else {
+        coupon.redemption_date
+    };
     coupon.tokens_to_redeem = reward;
     coupon.coupon_bump = *ctx.bumps.get("coupon").unwrap();
-    coupon.id = id_data;
+    coupon.label = label_data;
+    //kept so the bond can be reversed inside the cancel window
+    coupon.bond_epoch = token_state.epoch_count;
+    coupon.quote_bonded_amount = amount_post_fee;
+    coupon.quote_reserve_amount = base_pool_received;
+    coupon.quote_surplus_amount = growth_pool_received;
+    coupon.max_reward = max_reward;
+    coupon.bonder_address = ctx.accounts.user.key();
+
+    //keep the owner's position in sync
+    let user_position = &mut ctx.accounts.user_position;
+    if user_position.owner_address == Pubkey::default() {
+        user_position.token_state_address = token_state.key();
+        user_position.owner_address = beneficiary;
+        user_position.user_position_bump = *ctx.bumps.get("user_position").unwrap();
+    }
+    user_position.open_coupon(reward, coupon.redemption_date, clock.unix_timestamp)?;
+    user_position.total_quote_bonded = user_position.total_quote_bonded.checked_add(amount_post_fee).or_arith_error()?;
     //if epoch transition, next epoch
     if epoch_transition {
         //next epoch, iterate current epoch to next epoch
@@ -287,14 +449,5 @@ pub fn handle(ctx: Context<Bond>, id: String, amount: u64, period_index: u8) ->
     else {
         token_state.current_epoch_emissions = token_state.current_epoch_emissions.checked_add(reward).or_arith_error()?;
     }
-    //apply vote if exists
-    //check for optional account
-    //if account exists and voting is enabled, add points
-    if token_state.voting_enabled_date > clock.unix_timestamp {
-        let vote_account = &mut ctx.accounts.bond_vote;
-        if let Some(vote) = vote_account {
-            vote.total_votes = vote.total_votes.checked_add(amount).or_arith_error()?;
-        }
-    }
     Ok(())
 }
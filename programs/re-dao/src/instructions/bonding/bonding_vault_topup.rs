@@ -2,13 +2,14 @@ use crate::errors::{CustomErrorCode, OrArithError};
 
 use crate::structs::{TokenTrackerBase, TokenState, BondVote};
 use crate::transfers::transfers;
+use crate::utils::token_extensions::transfer_fee;
 use crate::utils::ascii_trim::TrimAsciiWhitespace;
 
 use anchor_lang::{
     prelude::{*},
     solana_program::system_program,
 };
-use anchor_spl::token::{TokenAccount, Token};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 #[instruction()]
@@ -34,30 +35,36 @@ pub struct BondingVaultTopup<'info> {
         seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
         bump = token_state.base_token_vault_bump
     )]
-    pub base_token_vault: Box<Account<'info, TokenAccount>>,
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         constraint = user_base_token.mint == token_state.base_mint_address,
         constraint = &user_base_token.owner == user.to_account_info().key
     )]
-    pub user_base_token: Box<Account<'info, TokenAccount>>,
-    #[account(address = anchor_spl::token::ID)]
-    pub token_program: Program<'info, Token>,
+    pub user_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = token_state.base_mint_address)]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.base_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
 pub fn handle(ctx: Context<BondingVaultTopup>, amount: u64) -> Result<()> {
     let token_state = &mut ctx.accounts.token_state;
-    //increment total topup
-    token_state.total_topup = token_state.total_topup.checked_add(amount).or_arith_error()?;
+    //increment total topup by what actually lands in the vault
+    let fee = transfer_fee(&ctx.accounts.base_mint.to_account_info(), amount)?;
+    let received = amount.checked_sub(fee).or_arith_error()?;
+    token_state.total_topup = token_state.total_topup.checked_add(received).or_arith_error()?;
     //transfer to bonding vault
     transfers::transfer(
         ctx.accounts.user.to_account_info(),
         ctx.accounts.user_base_token.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
         ctx.accounts.base_token_vault.to_account_info(),
         ctx.accounts.token_program.to_account_info(),
         amount,
+        token_state.base_decimals,
     )?;
     Ok(())
 }
@@ -0,0 +1,172 @@
+use crate::calculations::calculations::floor_price;
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, BondVote, UserPosition};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+use crate::utils::token_extensions::is_native_mint;
+
+use anchor_lang::{prelude::*, solana_program::system_program};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct CancelBond<'info> {
+    //the wallet that paid for the bond gets the refund, not whoever holds the coupon
+    #[account(
+        mut,
+        address = coupon.bonder_address
+    )]
+    pub payer: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    //position bond credited, cancellable coupons were never transferred so it's still the owner's
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), coupon.redeemer_address.as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    #[account(
+        mut,
+        constraint = payer_quote_token.mint == token_state.quote_mint_address,
+        constraint = payer_quote_token.owner == payer.key()
+    )]
+    //left out for native SOL, the refund is unwrapped to the payer's lamports instead
+    pub payer_quote_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    //temporary wSOL account the native refund passes through, closed to the payer in the same instruction
+    #[account(
+        init,
+        token::mint = quote_mint,
+        token::authority = token_state,
+        token::token_program = token_program,
+        seeds = [token_state.key().as_ref(), b"refund".as_ref(), coupon.key().as_ref()],
+        bump,
+        payer = payer
+    )]
+    pub refund_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        address = token_state.quote_reserve_token_address,
+    )]
+    pub quote_reserve_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = token_state.quote_surplus_token_address,
+    )]
+    pub quote_surplus_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = token_state.quote_mint_address)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    //required when the coupon has voted, the weight is taken back
+    #[account(
+        mut,
+        address = coupon.vote_cast
+    )]
+    pub bond_vote: Option<Box<Account<'info, BondVote>>>,
+    #[account(address = token_state.quote_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+//reverses everything bond added to the token state and refunds the post fee quote, fees are kept
+pub fn handle(ctx: Context<CancelBond>) -> Result<()> {
+    let base = &ctx.accounts.token_tracker_base;
+    let token_state = &mut ctx.accounts.token_state;
+    let coupon = &ctx.accounts.coupon;
+
+    if token_state.cancel_window == 0 {
+        return Err(error!(CustomErrorCode::CancelDisabledError));
+    }
+    //the refund goes to the payer's quote account, or through refund_token when bonded with native SOL
+    let refund_to = match (&ctx.accounts.payer_quote_token, &ctx.accounts.refund_token) {
+        (Some(payer_quote_token), _) => payer_quote_token.to_account_info(),
+        (None, Some(refund_token)) if is_native_mint(&token_state.quote_mint_address) => refund_token.to_account_info(),
+        _ => return Err(error!(CustomErrorCode::MissingQuoteTokenError)),
+    };
+    if coupon.is_redeemed || coupon.claimed_so_far > 0 {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+    //split, merged, rolled over and transferred coupons have no bond to reverse, a halving since can't be undone
+    let clock = Clock::get()?;
+    let window_end = coupon.bond_date.checked_add(token_state.cancel_window.into()).or_arith_error()?;
+    if clock.unix_timestamp > window_end || coupon.quote_bonded_amount == 0 || coupon.bond_epoch != token_state.epoch_count {
+        return Err(error!(CustomErrorCode::CancelWindowError));
+    }
+
+    if coupon.vote_cast != Pubkey::default() {
+        match ctx.accounts.bond_vote.as_mut() {
+            Some(bond_vote) => {
+                bond_vote.total_votes = bond_vote.total_votes.checked_sub(coupon.vote_weight).or_arith_error()?;
+            }
+            None => return Err(error!(CustomErrorCode::InvalidCouponError)),
+        }
+    }
+
+    //reverse the bond
+    let tokens = coupon.tokens_to_redeem;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_sub(tokens).or_arith_error()?;
+    token_state.total_emissions = token_state.total_emissions.checked_sub(tokens).or_arith_error()?;
+    token_state.current_epoch_emissions = token_state.current_epoch_emissions.checked_sub(tokens).or_arith_error()?;
+    token_state.mps = token_state.mps.checked_sub(coupon.max_reward).or_arith_error()?;
+    token_state.quote_bonded = token_state.quote_bonded.checked_sub(coupon.quote_bonded_amount).or_arith_error()?;
+    token_state.total_reserve = token_state.total_reserve.checked_sub(coupon.quote_reserve_amount).or_arith_error()?;
+    token_state.total_surplus_reserve = token_state.total_surplus_reserve.checked_sub(coupon.quote_surplus_amount).or_arith_error()?;
+    token_state.floor_price = floor_price(token_state.quote_bonded, token_state.mps, token_state.base_decimals)?;
+    token_state.total_cancelled = token_state.total_cancelled.checked_add(1).or_arith_error()?;
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.close_coupon(tokens)?;
+    user_position.total_quote_bonded = user_position.total_quote_bonded.checked_sub(coupon.quote_bonded_amount).or_arith_error()?;
+
+    //refund from the reserve and surplus, signed by the token state as owner or delegate
+    let id = token_state.id.as_ref();
+    let base_key = base.key();
+    let seeds = &[
+        base_key.as_ref(),
+        id.trim_ascii_whitespace(),
+        &[token_state.token_state_bump],
+    ];
+    for (vault, amount) in [
+        (&ctx.accounts.quote_reserve_token_address, coupon.quote_reserve_amount),
+        (&ctx.accounts.quote_surplus_token_address, coupon.quote_surplus_amount),
+    ] {
+        if amount > 0 {
+            transfers::transfer_with_signer(
+                token_state.to_account_info(),
+                vault.to_account_info(),
+                ctx.accounts.quote_mint.to_account_info(),
+                refund_to.clone(),
+                ctx.accounts.token_program.to_account_info(),
+                amount,
+                token_state.quote_decimals,
+                seeds,
+            )?;
+        }
+    }
+    //unwrap, closing the wSOL account hands the refund and its rent to the payer
+    if ctx.accounts.payer_quote_token.is_none() {
+        transfers::close_with_signer(
+            token_state.to_account_info(),
+            refund_to,
+            ctx.accounts.payer.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            seeds,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+//permissionless, anyone can push a matured coupon out to its owner
+#[derive(Accounts)]
+pub struct CrankRedeem<'info> {
+    //pays for the owner's token account if missing, receives the coupon rent as the tip
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    /// CHECK: coupon owner, only receives tokens
+    #[account(address = coupon.redeemer_address)]
+    pub owner: UncheckedAccount<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        close = cranker,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), owner.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    #[account(
+        init_if_needed,
+        associated_token::mint = base_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+        payer = cranker
+    )]
+    pub owner_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
+        bump = token_state.base_token_vault_bump
+    )]
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = token_state.base_mint_address
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.base_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+//only fully matured coupons, whatever linear vesting hasn't paid out yet is sent along
+pub fn handle(ctx: Context<CrankRedeem>) -> Result<()> {
+    let base = &ctx.accounts.token_tracker_base;
+    let token_state = &mut ctx.accounts.token_state;
+    let coupon = &ctx.accounts.coupon;
+
+    if coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= coupon.redemption_date {
+        return Err(error!(CustomErrorCode::CouponDateError));
+    }
+    let remaining = coupon.tokens_to_redeem.checked_sub(coupon.claimed_so_far).or_arith_error()?;
+
+    let id = token_state.id.as_ref();
+    let base_key = base.key();
+    let seeds = &[
+        base_key.as_ref(),
+        id.trim_ascii_whitespace(),
+        &[token_state.token_state_bump],
+    ];
+    transfers::release_base_with_signer(
+        token_state.mint_mode,
+        token_state.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.base_token_vault.to_account_info(),
+        ctx.accounts.owner_base_token.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        remaining,
+        token_state.base_decimals,
+        seeds,
+    )?;
+
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.redeem(remaining)?;
+    user_position.close_coupon(0)?;
+
+    //update token state, the coupon is closed to the cranker on exit
+    token_state.total_redeemed = token_state.total_redeemed.checked_add(remaining).or_arith_error()?;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_sub(remaining).or_arith_error()?;
+
+    Ok(())
+}
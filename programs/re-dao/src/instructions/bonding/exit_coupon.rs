@@ -0,0 +1,127 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+
+use crate::calculations::calculations;
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ExitCoupon<'info> {
+    #[account(
+        mut,
+        address = coupon.redeemer_address,
+    )]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), user.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    #[account(
+        mut,
+        constraint = user_base_token.mint == token_state.base_mint_address,
+        constraint = &user_base_token.owner == user.to_account_info().key
+    )]
+    pub user_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
+        bump = token_state.base_token_vault_bump
+    )]
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = token_state.base_mint_address
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.base_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+
+//redeem before maturity, the penalty stays in the vault or is never minted in mint mode
+pub fn handle(ctx: Context<ExitCoupon>) -> Result<()> {
+    let base = &mut ctx.accounts.token_tracker_base;
+    let token_state = &mut ctx.accounts.token_state;
+    let coupon = &mut ctx.accounts.coupon;
+
+    if !token_state.early_exit_enabled {
+        return Err(error!(CustomErrorCode::EarlyExitDisabledError));
+    }
+    if coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+
+    //linear coupons may already be partially claimed
+    let remaining = coupon.tokens_to_redeem.checked_sub(coupon.claimed_so_far).or_arith_error()?;
+    let clock = Clock::get()?;
+    let penalty = calculations::early_exit_penalty(
+        remaining,
+        token_state.early_exit_penalty,
+        coupon.bond_date,
+        coupon.redemption_date,
+        clock.unix_timestamp,
+        token_state.fee_bps,
+    )?;
+    let payout = remaining.checked_sub(penalty).or_arith_error()?;
+
+    if payout > 0 {
+        let id = token_state.id.as_ref();
+        let base_key = base.key();
+        let seeds = &[
+            base_key.as_ref(),
+            id.trim_ascii_whitespace(),
+            &[token_state.token_state_bump],
+        ];
+        transfers::release_base_with_signer(
+            token_state.mint_mode,
+            token_state.to_account_info(),
+            ctx.accounts.base_mint.to_account_info(),
+            ctx.accounts.base_token_vault.to_account_info(),
+            ctx.accounts.user_base_token.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            payout,
+            token_state.base_decimals,
+            seeds,
+        )?;
+    }
+    coupon.claimed_so_far = coupon.tokens_to_redeem;
+    coupon.is_redeemed = true;
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.redeem(payout)?;
+    user_position.close_coupon(penalty)?;
+
+    //update token state
+    token_state.total_redeemed = token_state.total_redeemed.checked_add(payout).or_arith_error()?;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_sub(remaining).or_arith_error()?;
+    token_state.total_forfeited = token_state.total_forfeited.checked_add(penalty).or_arith_error()?;
+    token_state.total_early_exits = token_state.total_early_exits.checked_add(1).or_arith_error()?;
+
+    Ok(())
+}
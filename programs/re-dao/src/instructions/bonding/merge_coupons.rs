@@ -0,0 +1,83 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct MergeCoupons<'info> {
+    #[account(
+        mut,
+        address = coupon.redeemer_address
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    //kept, receives the other coupon's tokens
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    //closed, rent goes back to the owner
+    #[account(
+        mut,
+        close = owner,
+        constraint = other_coupon.redeemer_address == owner.key() @ CustomErrorCode::InvalidCouponError,
+        constraint = other_coupon.key() != coupon.key() @ CustomErrorCode::InvalidCouponError,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), other_coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = other_coupon.coupon_bump,
+    )]
+    pub other_coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), owner.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+}
+
+pub fn handle(ctx: Context<MergeCoupons>) -> Result<()> {
+    let coupon = &mut ctx.accounts.coupon;
+    let other_coupon = &ctx.accounts.other_coupon;
+    if coupon.is_redeemed || other_coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+    if coupon.claimed_so_far > 0 || other_coupon.claimed_so_far > 0 {
+        return Err(error!(CustomErrorCode::InvalidCouponError));
+    }
+    coupon.tokens_to_redeem = coupon.tokens_to_redeem.checked_add(other_coupon.tokens_to_redeem).or_arith_error()?;
+    //the merged coupon matures with the later of the two
+    if other_coupon.redemption_date > coupon.redemption_date {
+        coupon.redemption_date = other_coupon.redemption_date;
+        coupon.period_index = other_coupon.period_index;
+    }
+    //strictest schedule of the two, cliff over linear and the later cliff, so neither coupon unlocks early
+    coupon.vesting_linear = coupon.vesting_linear && other_coupon.vesting_linear;
+    coupon.cliff_date = if coupon.vesting_linear {
+        coupon.cliff_date.max(other_coupon.cliff_date)
+    } else {
+        coupon.redemption_date
+    };
+    //keep the later start so the lock, and with it the early exit penalty, can't be stretched
+    coupon.bond_date = coupon.bond_date.max(other_coupon.bond_date);
+    //carry a cast vote over so the merged coupon can't vote twice
+    if coupon.vote_cast == Pubkey::default() {
+        coupon.vote_cast = other_coupon.vote_cast;
+    }
+    //the merged coupon can't be cancelled
+    coupon.quote_bonded_amount = 0;
+    coupon.vote_weight = coupon.vote_weight.checked_add(other_coupon.vote_weight).or_arith_error()?;
+    //one coupon less, the pending total doesn't change
+    ctx.accounts.user_position.close_coupon(0)?;
+    Ok(())
+}
@@ -1,7 +1,25 @@
 pub mod bond;
 pub mod bonding_vault_topup;
 pub mod redeem_coupon;
+pub mod solvency_ratio;
+pub mod transfer_coupon;
+pub mod split_coupon;
+pub mod merge_coupons;
+pub mod exit_coupon;
+pub mod redeem_many;
+pub mod crank_redeem;
+pub mod rollover_coupon;
+pub mod cancel_bond;
 
 pub use bond::*;
 pub use bonding_vault_topup::*;
-pub use redeem_coupon::*;
\ No newline at end of file
+pub use redeem_coupon::*;
+pub use solvency_ratio::*;
+pub use transfer_coupon::*;
+pub use split_coupon::*;
+pub use merge_coupons::*;
+pub use exit_coupon::*;
+pub use redeem_many::*;
+pub use crank_redeem::*;
+pub use rollover_coupon::*;
+pub use cancel_bond::*;
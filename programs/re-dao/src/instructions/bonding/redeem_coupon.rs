@@ -1,6 +1,7 @@
 use crate::errors::{CustomErrorCode, OrArithError};
 
-use crate::structs::{TokenTrackerBase, TokenState, BondVote, BondCoupon};
+use crate::calculations::calculations;
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
 use crate::transfers::transfers;
 use crate::utils::ascii_trim::TrimAsciiWhitespace;
 
@@ -8,11 +9,9 @@ use anchor_lang::{
     prelude::{*},
     solana_program::system_program,
 };
-use anchor_spl::token::{TokenAccount, Token, self};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
-#[instruction(id: String)]
-
 pub struct RedeemCoupon<'info> {
     #[account(
         mut,
@@ -32,71 +31,103 @@ pub struct RedeemCoupon<'info> {
     pub token_state: Box<Account<'info, TokenState>>,
     #[account(
         mut,
-        seeds = [token_state.key().as_ref(), user.key().as_ref(), id.as_bytes()],
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
         bump = coupon.coupon_bump,
     )]
     pub coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), user.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
     #[account(
         mut,
         constraint = &user_base_token.mint == &token_state.base_mint_address,
         constraint = &user_base_token.owner == user.to_account_info().key
     )]
-    pub user_base_token: Box<Account<'info, TokenAccount>>,
+    pub user_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
         seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
         bump = token_state.base_token_vault_bump
     )]
-    pub base_token_vault: Box<Account<'info, TokenAccount>>,
-    pub token_program: Program<'info, Token>,
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = token_state.base_mint_address
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.base_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
 
-pub fn handle(ctx: Context<RedeemCoupon>, id: String) -> Result<()> {
+pub fn handle(ctx: Context<RedeemCoupon>) -> Result<()> {
     
     //check redemption date
     let base = &mut ctx.accounts.token_tracker_base;
     let token_state = &mut ctx.accounts.token_state;
     let coupon = &mut ctx.accounts.coupon;
 
+    if coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+
     let clock = Clock::get()?;
-    if clock.unix_timestamp > coupon.redemption_date {
-        //send base tokens to user
-        if !coupon.is_redeemed { 
-            let id = token_state.id.as_ref();
-            let base_key = base.key();
-            let seeds = &[
-                base_key.as_ref(),
-                id.trim_ascii_whitespace(),
-                &[token_state.token_state_bump],
-            ];
-            transfers::transfer_with_signer(
-               token_state.to_account_info(),
-                ctx.accounts.base_token_vault.to_account_info(),
-                ctx.accounts.user_base_token.to_account_info(),
-                ctx.accounts.token_program.to_account_info(),
-                coupon.tokens_to_redeem,
-                seeds,
-            )?;
-            //is_redeeemed = true
-            coupon.is_redeemed = true;
+    //cliff coupons pay out everything after maturity, linear ones whatever vested since the last claim
+    let vested = if coupon.vesting_linear {
+        calculations::vested_amount(
+            coupon.tokens_to_redeem,
+            coupon.bond_date,
+            coupon.cliff_date,
+            coupon.redemption_date,
+            clock.unix_timestamp,
+        )?
+    } else if clock.unix_timestamp > coupon.redemption_date {
+        coupon.tokens_to_redeem
+    } else {
+        return Err(error!(CustomErrorCode::CouponDateError));
+    };
+    let claimable = vested.saturating_sub(coupon.claimed_so_far);
+    if claimable == 0 {
+        return Err(error!(CustomErrorCode::NothingToClaimError));
+    }
 
-            //update token state
-            token_state.total_redeemed = token_state.total_redeemed.checked_add(coupon.tokens_to_redeem).or_arith_error()?;
+    //send base tokens to user
+    let id = token_state.id.as_ref();
+    let base_key = base.key();
+    let seeds = &[
+        base_key.as_ref(),
+        id.trim_ascii_whitespace(),
+        &[token_state.token_state_bump],
+    ];
+    transfers::release_base_with_signer(
+        token_state.mint_mode,
+        token_state.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.base_token_vault.to_account_info(),
+        ctx.accounts.user_base_token.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        claimable,
+        token_state.base_decimals,
+        seeds,
+    )?;
+    coupon.claimed_so_far = coupon.claimed_so_far.checked_add(claimable).or_arith_error()?;
+    coupon.is_redeemed = coupon.claimed_so_far == coupon.tokens_to_redeem;
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.redeem(claimable)?;
 
-            //close account -> send sol to user
+    //update token state
+    token_state.total_redeemed = token_state.total_redeemed.checked_add(claimable).or_arith_error()?;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_sub(claimable).or_arith_error()?;
 
-        } else {
-            return Err(error!(CustomErrorCode::CouponClaimedError));
-            //throw
-        }
-        
-    } else {
-        return Err(error!(CustomErrorCode::CouponDateError));
-        //throw
+    //fully paid out, close the coupon and send the rent to the user like redeem_many does
+    if coupon.is_redeemed {
+        user_position.close_coupon(0)?;
+        coupon.close(ctx.accounts.user.to_account_info())?;
     }
-    
     Ok(())
-}
\ No newline at end of file
+}
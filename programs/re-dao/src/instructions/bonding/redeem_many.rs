@@ -0,0 +1,143 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+
+use crate::calculations::calculations;
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+//coupons to redeem are passed as writable remaining accounts
+#[derive(Accounts)]
+pub struct RedeemMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), user.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    #[account(
+        mut,
+        constraint = user_base_token.mint == token_state.base_mint_address,
+        constraint = &user_base_token.owner == user.to_account_info().key
+    )]
+    pub user_base_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
+        bump = token_state.base_token_vault_bump
+    )]
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = token_state.base_mint_address
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.base_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, RedeemMany<'info>>) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    let token_state_key = ctx.accounts.token_state.key();
+    let clock = Clock::get()?;
+
+    let mut total_claimable: u64 = 0;
+    let mut closed_coupons: u64 = 0;
+    for coupon_info in ctx.remaining_accounts.iter() {
+        //closed earlier in this instruction, the coupon was passed twice
+        if coupon_info.data_is_empty() {
+            continue;
+        }
+        let mut coupon: Account<'info, BondCoupon> = Account::try_from(coupon_info)?;
+        if coupon.token_state_address != token_state_key || coupon.redeemer_address != user {
+            return Err(error!(CustomErrorCode::InvalidCouponError));
+        }
+        if coupon.is_redeemed {
+            continue;
+        }
+        let vested = if coupon.vesting_linear {
+            calculations::vested_amount(
+                coupon.tokens_to_redeem,
+                coupon.bond_date,
+                coupon.cliff_date,
+                coupon.redemption_date,
+                clock.unix_timestamp,
+            )?
+        } else if clock.unix_timestamp > coupon.redemption_date {
+            coupon.tokens_to_redeem
+        } else {
+            0
+        };
+        let claimable = vested.saturating_sub(coupon.claimed_so_far);
+        if claimable == 0 {
+            msg!("Coupon {} has nothing to claim yet, skipped", coupon_info.key());
+            continue;
+        }
+        coupon.claimed_so_far = coupon.claimed_so_far.checked_add(claimable).or_arith_error()?;
+        total_claimable = total_claimable.checked_add(claimable).or_arith_error()?;
+        //fully paid out coupons are closed, partially vested ones stay open for the next claim
+        if coupon.claimed_so_far == coupon.tokens_to_redeem {
+            coupon.close(ctx.accounts.user.to_account_info())?;
+            closed_coupons = closed_coupons.checked_add(1).or_arith_error()?;
+        } else {
+            coupon.exit(ctx.program_id)?;
+        }
+    }
+    if total_claimable == 0 {
+        return Err(error!(CustomErrorCode::NothingToClaimError));
+    }
+
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.redeem(total_claimable)?;
+    for _ in 0..closed_coupons {
+        user_position.close_coupon(0)?;
+    }
+
+    //one transfer for all coupons
+    let base = &ctx.accounts.token_tracker_base;
+    let token_state = &mut ctx.accounts.token_state;
+    let id = token_state.id.as_ref();
+    let base_key = base.key();
+    let seeds = &[
+        base_key.as_ref(),
+        id.trim_ascii_whitespace(),
+        &[token_state.token_state_bump],
+    ];
+    transfers::release_base_with_signer(
+        token_state.mint_mode,
+        token_state.to_account_info(),
+        ctx.accounts.base_mint.to_account_info(),
+        ctx.accounts.base_token_vault.to_account_info(),
+        ctx.accounts.user_base_token.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        total_claimable,
+        token_state.base_decimals,
+        seeds,
+    )?;
+
+    //update token state
+    token_state.total_redeemed = token_state.total_redeemed.checked_add(total_claimable).or_arith_error()?;
+    token_state.outstanding_liabilities = token_state.outstanding_liabilities.checked_sub(total_claimable).or_arith_error()?;
+
+    Ok(())
+}
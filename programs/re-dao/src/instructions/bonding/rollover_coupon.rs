@@ -0,0 +1,141 @@
+use crate::calculations::calculations::{floor_price, max_period_length, rollover_bonus, vesting_cliff};
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction(period_index: u8)]
+pub struct RolloverCoupon<'info> {
+    #[account(
+        mut,
+        address = coupon.redeemer_address
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    //matured coupon, closed once rolled over
+    #[account(
+        mut,
+        close = owner,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        init,
+        seeds = [
+            token_state.key().as_ref(),
+            b"coupon".as_ref(),
+            token_state.bond_coupon_count
+            .checked_add(1)
+            .or_arith_error()?
+            .to_le_bytes().as_ref()],
+        bump,
+        payer = owner,
+        space=420
+    )]
+    pub new_coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), owner.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+//the unclaimed tokens of a matured coupon become the principal of a new lock, no quote changes hands
+pub fn handle(ctx: Context<RolloverCoupon>, period_index: u8) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    let coupon = &ctx.accounts.coupon;
+    if token_state.rollover_multiplier == 0 {
+        return Err(error!(CustomErrorCode::RolloverDisabledError));
+    }
+    if token_state.delisted {
+        return Err(error!(CustomErrorCode::DelistedError));
+    }
+    if period_index as usize >= token_state.period_enabled.len() {
+        return Err(error!(CustomErrorCode::PeriodLengthError));
+    }
+    if !token_state.period_enabled[period_index as usize] {
+        return Err(error!(CustomErrorCode::DisabledPeriodError));
+    }
+    if coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+    let clock = Clock::get()?;
+    if clock.unix_timestamp <= coupon.redemption_date {
+        return Err(error!(CustomErrorCode::CouponDateError));
+    }
+    let principal = coupon.tokens_to_redeem.checked_sub(coupon.claimed_so_far).or_arith_error()?;
+    if principal == 0 {
+        return Err(error!(CustomErrorCode::NothingToClaimError));
+    }
+
+    //the bonus is new emissions scaled by the chosen period's length, capped so a rollover never crosses a halving
+    let period_length = token_state.period_lengths[period_index as usize];
+    let max_lock = max_period_length(&token_state.period_lengths, &token_state.period_enabled);
+    let bonus = rollover_bonus(principal, token_state.rollover_multiplier, token_state.reward_bps, period_length, max_lock)?;
+    let epoch_left = token_state.next_halving.saturating_sub(token_state.total_emissions);
+    let bonus = bonus.min(epoch_left);
+
+    //base vault has to cover the bonus as well
+    let new_outstanding_liabilities = token_state
+        .outstanding_liabilities
+        .checked_add(bonus)
+        .or_arith_error()?;
+    if token_state.enforce_solvency && !token_state.mint_mode {
+        //payouts can exceed topups when the vault is funded some other way
+        let available = token_state.total_topup.saturating_sub(token_state.total_redeemed);
+        if new_outstanding_liabilities > available {
+            return Err(error!(CustomErrorCode::InsolventVaultError));
+        }
+    }
+    token_state.outstanding_liabilities = new_outstanding_liabilities;
+    token_state.total_emissions = token_state.total_emissions.checked_add(bonus).or_arith_error()?;
+    token_state.current_epoch_emissions = token_state.current_epoch_emissions.checked_add(bonus).or_arith_error()?;
+    token_state.mps = token_state.mps.checked_add(bonus).or_arith_error()?;
+    token_state.floor_price = floor_price(token_state.quote_bonded, token_state.mps, token_state.base_decimals)?;
+
+    //create the new coupon
+    token_state.bond_coupon_count = token_state.bond_coupon_count.checked_add(1).or_arith_error()?;
+    let new_coupon = &mut ctx.accounts.new_coupon;
+    new_coupon.is_redeemed = false;
+    new_coupon.coupon_count = token_state.bond_coupon_count;
+    new_coupon.period_index = period_index;
+    new_coupon.token_state_address = coupon.token_state_address;
+    new_coupon.redeemer_address = coupon.redeemer_address;
+    new_coupon.bond_date = clock.unix_timestamp;
+    new_coupon.redemption_date = clock.unix_timestamp.checked_add(period_length).or_arith_error()?;
+    new_coupon.tokens_to_redeem = principal.checked_add(bonus).or_arith_error()?;
+    new_coupon.coupon_bump = *ctx.bumps.get("new_coupon").unwrap();
+    new_coupon.label = coupon.label;
+    new_coupon.vesting_linear = token_state.period_vesting_linear[period_index as usize];
+    new_coupon.cliff_date = if new_coupon.vesting_linear {
+        vesting_cliff(new_coupon.bond_date, new_coupon.redemption_date, token_state.period_cliff_fraction[period_index as usize], token_state.fee_bps)?
+    } else {
+        new_coupon.redemption_date
+    };
+    //a vote already cast with the old coupon can't be cast again
+    new_coupon.vote_cast = coupon.vote_cast;
+    new_coupon.vote_weight = 0;
+    let user_position = &mut ctx.accounts.user_position;
+    user_position.close_coupon(principal)?;
+    user_position.open_coupon(new_coupon.tokens_to_redeem, new_coupon.redemption_date, clock.unix_timestamp)?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::calculations::calculations::solvency_ratio;
+
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+//returns (topped up - redeemed) / outstanding liabilities scaled by fee_bps, u64::MAX when nothing is owed or in mint mode
+#[derive(Accounts)]
+#[instruction()]
+pub struct SolvencyRatio<'info> {
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+pub fn handle(ctx: Context<SolvencyRatio>) -> Result<u64> {
+    let token_state = &ctx.accounts.token_state;
+    if token_state.mint_mode {
+        return Ok(u64::MAX);
+    }
+    solvency_ratio(
+        token_state.total_topup,
+        token_state.total_redeemed,
+        token_state.outstanding_liabilities,
+        token_state.fee_bps.into(),
+    )
+}
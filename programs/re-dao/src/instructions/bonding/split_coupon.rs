@@ -0,0 +1,95 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct SplitCoupon<'info> {
+    #[account(
+        mut,
+        address = coupon.redeemer_address
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        init,
+        seeds = [
+            token_state.key().as_ref(),
+            b"coupon".as_ref(),
+            token_state.bond_coupon_count
+            .checked_add(1)
+            .or_arith_error()?
+            .to_le_bytes().as_ref()],
+        bump,
+        payer = owner,
+        space=420
+    )]
+    pub new_coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), owner.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+//moves amount of the coupon into a new coupon with the same maturity
+pub fn handle(ctx: Context<SplitCoupon>, amount: u64) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    let coupon = &mut ctx.accounts.coupon;
+    if coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+    //partially claimed coupons can't be split, the claimed amount would be ambiguous
+    if amount == 0 || amount >= coupon.tokens_to_redeem || coupon.claimed_so_far > 0 {
+        return Err(error!(CustomErrorCode::InvalidCouponError));
+    }
+    coupon.tokens_to_redeem = coupon.tokens_to_redeem.checked_sub(amount).or_arith_error()?;
+    //the bond no longer maps to a single coupon, it can't be cancelled
+    coupon.quote_bonded_amount = 0;
+
+    token_state.bond_coupon_count = token_state.bond_coupon_count.checked_add(1).or_arith_error()?;
+    let new_coupon = &mut ctx.accounts.new_coupon;
+    new_coupon.is_redeemed = false;
+    new_coupon.coupon_count = token_state.bond_coupon_count;
+    new_coupon.period_index = coupon.period_index;
+    new_coupon.token_state_address = coupon.token_state_address;
+    new_coupon.redeemer_address = coupon.redeemer_address;
+    new_coupon.redemption_date = coupon.redemption_date;
+    new_coupon.bond_date = coupon.bond_date;
+    new_coupon.vesting_linear = coupon.vesting_linear;
+    new_coupon.cliff_date = coupon.cliff_date;
+    new_coupon.tokens_to_redeem = amount;
+    new_coupon.coupon_bump = *ctx.bumps.get("new_coupon").unwrap();
+    new_coupon.label = coupon.label;
+    //the vote weight stays on the original, the child can't vote again
+    new_coupon.vote_cast = coupon.vote_cast;
+    new_coupon.vote_weight = 0;
+    //one more coupon, the pending total doesn't change
+    let clock = Clock::get()?;
+    ctx.accounts.user_position.open_coupon(0, new_coupon.redemption_date, clock.unix_timestamp)?;
+    Ok(())
+}
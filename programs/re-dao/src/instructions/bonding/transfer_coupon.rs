@@ -0,0 +1,79 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon, UserPosition};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferCoupon<'info> {
+    #[account(
+        mut,
+        address = coupon.redeemer_address
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"coupon".as_ref(), coupon.coupon_count.to_le_bytes().as_ref()],
+        bump = coupon.coupon_bump,
+    )]
+    pub coupon: Box<Account<'info, BondCoupon>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), owner.key().as_ref()],
+        bump = user_position.user_position_bump,
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    //created if the new owner has no coupons yet
+    #[account(
+        init_if_needed,
+        seeds = [token_state.key().as_ref(), b"position".as_ref(), new_owner.as_ref()],
+        bump,
+        payer = owner,
+        space=150
+    )]
+    pub new_owner_position: Box<Account<'info, UserPosition>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+//hands the redemption rights to new_owner, a vote already cast stays with the coupon
+pub fn handle(ctx: Context<TransferCoupon>, new_owner: Pubkey) -> Result<()> {
+    let coupon = &mut ctx.accounts.coupon;
+    if coupon.is_redeemed {
+        return Err(error!(CustomErrorCode::CouponClaimedError));
+    }
+    //both positions would be the same account
+    if new_owner == coupon.redeemer_address {
+        return Err(error!(CustomErrorCode::InvalidCouponError));
+    }
+    coupon.redeemer_address = new_owner;
+    //the new owner didn't pay for the bond, it can't be cancelled anymore
+    coupon.quote_bonded_amount = 0;
+
+    //what is still owed moves with the coupon, quote bonded stays with the original bonder
+    let remaining = coupon.tokens_to_redeem.checked_sub(coupon.claimed_so_far).or_arith_error()?;
+    ctx.accounts.user_position.close_coupon(remaining)?;
+    let new_owner_position = &mut ctx.accounts.new_owner_position;
+    if new_owner_position.owner_address == Pubkey::default() {
+        new_owner_position.token_state_address = ctx.accounts.token_state.key();
+        new_owner_position.owner_address = new_owner;
+        new_owner_position.user_position_bump = *ctx.bumps.get("new_owner_position").unwrap();
+    }
+    let clock = Clock::get()?;
+    new_owner_position.open_coupon(remaining, coupon.redemption_date, clock.unix_timestamp)?;
+    Ok(())
+}
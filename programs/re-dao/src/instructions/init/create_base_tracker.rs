@@ -14,7 +14,7 @@ use {anchor_lang::{
 pub struct CreateBaseTracker<'info> {
     #[account(
         mut,
-        address = AUTH.parse::<Pubkey>().unwrap(),
+        address = AUTH,
     )]
     pub creator: Signer<'info>,
     #[account(
@@ -50,17 +50,22 @@ pub fn handle(ctx: Context<CreateBaseTracker>, id: String) -> Result<()> {
 
     //set auth
     tracker.auth_wallet = ctx.accounts.creator.key();
+    tracker.pending_auth_wallet = Pubkey::default();
 
     //set receive wallet
+    tracker.receive_mint = ctx.accounts.payment_mint.key();
     tracker.receive_token_account = ctx.accounts.payment_token_address.key();
 
     //cost - TODO -> is it maybe better to funnel this through bonding? must bond at least x SOL for creating
     tracker.cost = (15 as u64)
         .checked_mul(LAMPORTS_PER_SOL)
         .or_arith_error()?;
+    tracker.vanity_costs = [0; 5];
+    tracker.protocol_fee_bps = 0;
 
     //enabled
     tracker.enabled = false;
+    tracker.permissionless = false;
     tracker.total_received = 0;
     Ok(())
 }
\ No newline at end of file
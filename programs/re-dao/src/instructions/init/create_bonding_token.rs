@@ -1,27 +1,29 @@
 use anchor_spl::token;
 
+use crate::calculations::calculations::{creation_cost, fees_exceed_total};
+use crate::constants::{MAX_PROTOCOL_FEE_BPS, PROTOCOL_FEE_BPS};
 use crate::errors::*;
 use crate::params::init::CreateBondingTokenParams;
-use crate::structs::TokenState;
+use crate::structs::{TokenState, ReservedId};
 use crate::structs::{
     token_tracker_base::TokenTrackerBase,
     token_tracker::TokenTracker
 };
+use crate::transfers::transfers;
 use crate::utils::ascii_trim::TrimAsciiWhitespace;
-use crate::constants::constants::AUTH;
+use crate::utils::token_extensions::validate_mint;
 use {anchor_lang::{
         prelude::{*},
-        solana_program::system_program,
+        solana_program::{program_option::COption, system_program},
     },
-    anchor_spl::token::{Mint, Token, TokenAccount, ID}
+    anchor_spl::token::{Token, ID},
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface}
 };
 #[derive(Accounts)]
 #[instruction(id: String)]
 pub struct CreateBondingToken<'info> {
-    #[account(
-        mut,
-        address = AUTH.parse::<Pubkey>().unwrap(),
-    )]
+    //tracker auth, or anyone paying the creation cost when the tracker is permissionless
+    #[account(mut)]
     pub creator: Signer<'info>,
     #[account(
         mut,
@@ -50,49 +52,67 @@ pub struct CreateBondingToken<'info> {
         space=2200
     )]
     pub token_state: Box<Account<'info, TokenState>>,
+    /// CHECK: reserved id PDA, only deserialized if it exists
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), b"reserved".as_ref(), id.as_bytes()],
+        bump,
+    )]
+    pub reserved_id: UncheckedAccount<'info>,
     #[account(
-        constraint = base_mint.to_account_info().owner == &ID,
+        constraint = base_mint.to_account_info().owner == base_token_program.key,
         constraint = base_mint.decimals >= 1,
-        constraint = base_mint.decimals == quote_mint.decimals 
     )]
-    pub base_mint: Box<Account<'info, Mint>>,
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(init,
         token::mint = base_mint,
         token::authority = token_state,
+        token::token_program = base_token_program,
         seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
         bump,
         payer = creator
     )]
-    pub base_token_vault: Box<Account<'info, TokenAccount>>,
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
-        constraint = quote_mint.to_account_info().owner == &ID,
         constraint = quote_mint.decimals >= 1,
-        constraint = quote_mint.decimals == base_mint.decimals
     )]
-    pub quote_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mut,
         constraint = &quote_reserve_token_address.mint == quote_mint.to_account_info().key
     )]
-    pub quote_reserve_token_address: Box<Account<'info, TokenAccount>>,
+    pub quote_reserve_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = &quote_reserve_token_address.mint == quote_mint.to_account_info().key
+        constraint = &quote_surplus_token_address.mint == quote_mint.to_account_info().key
     )]
-    pub quote_surplus_token_address: Box<Account<'info, TokenAccount>>,
+    pub quote_surplus_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = &quote_reserve_token_address.mint == quote_mint.to_account_info().key
+        constraint = &quote_runway_token_address.mint == quote_mint.to_account_info().key
     )]
-    pub quote_runway_token_address: Box<Account<'info, TokenAccount>>,
+    pub quote_runway_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    //creation cost payment, only needed when the creator isn't the tracker auth
+    #[account(
+        mut,
+        constraint = creator_payment_token.mint == token_tracker_base.receive_mint,
+        constraint = &creator_payment_token.owner == creator.to_account_info().key
+    )]
+    pub creator_payment_token: Option<Box<Account<'info, token::TokenAccount>>>,
+    #[account(
+        mut,
+        address = token_tracker_base.receive_token_account
+    )]
+    pub receive_token_account: Option<Box<Account<'info, token::TokenAccount>>>,
+    //creation cost is paid in the tracker's legacy spl receive mint
     #[account(address = ID)]
     pub token_program: Program<'info, Token>,
+    pub base_token_program: Interface<'info, TokenInterface>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
-fn validate(params: CreateBondingTokenParams) -> Result<()> {
+pub(crate) fn validate(params: CreateBondingTokenParams) -> Result<()> {
     if params.next_halving == 0 {
         return Err(error!(CustomErrorCode::ZeroError));
     }
@@ -125,47 +145,71 @@ fn validate(params: CreateBondingTokenParams) -> Result<()> {
     //max decimals vs supply max_decimals = floor(log10(2^64/max_whole_units_supply))
     Ok(())
 }
-#[access_control(validate(params))]
-pub fn handle(ctx: Context<CreateBondingToken>, id: String, params: CreateBondingTokenParams) -> Result<()> {
-
+pub(crate) fn id_data(id: &str) -> Result<[u8; 20]> {
     let id_bytes = id.as_bytes();
     if id_bytes.len() >= 20 {
         return Err(error!(CustomErrorCode::InvalidIdLength));
     }
     let mut id_data = [b' '; 20];
     id_data[..id_bytes.len()].copy_from_slice(id_bytes);
+    Ok(id_data)
+}
 
-    //TODO, add vanity ID costs.
-
-    let token_state = &mut ctx.accounts.token_state;
-    let token_tracker = &mut ctx.accounts.token_tracker;
-    let token_tracker_base = &mut ctx.accounts.token_tracker_base;
-
-    if token_tracker_base.index == 0 {
-        if ctx.accounts.creator.key() != AUTH.parse::<Pubkey>().unwrap() {
-            return Err(error!(CustomErrorCode::InvalidCreator));
-        }
+//permissionless creators pay the creation cost and control their own token state
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn charge_creation_cost<'info>(
+    creator: &Signer<'info>,
+    token_tracker_base: &mut TokenTrackerBase,
+    reserved_id: &UncheckedAccount<'info>,
+    creator_payment_token: &Option<Box<Account<'info, token::TokenAccount>>>,
+    receive_token_account: &Option<Box<Account<'info, token::TokenAccount>>>,
+    token_program: &Program<'info, Token>,
+    id_length: usize,
+) -> Result<()> {
+    if creator.key() == token_tracker_base.auth_wallet {
+        return Ok(());
     }
-    token_tracker_base.index = token_tracker_base.index.checked_add(1).or_arith_error()?;
-    token_tracker.token_state = token_state.key();
-    token_tracker.id = id_data;
-    token_tracker.index = token_tracker_base.index;
-    token_tracker.token_tracker_bump = *ctx.bumps.get("token_tracker").unwrap();
-
-    //
-    token_state.base_mint_address = ctx.accounts.base_mint.key();
-    token_state.base_mint_token_address = ctx.accounts.base_token_vault.key();
-    token_state.quote_mint_address = ctx.accounts.quote_mint.key();
-    token_state.quote_reserve_token_address = ctx.accounts.quote_reserve_token_address.key();
-    token_state.quote_surplus_token_address = ctx.accounts.quote_surplus_token_address.key();
-    token_state.quote_runway_token_address = ctx.accounts.quote_runway_token_address.key();
-    if token_state.base_mint_address == token_state.quote_mint_address {
-        return Err(error!(CustomErrorCode::BaseAndQuoteMatch));
+    if !token_tracker_base.permissionless {
+        return Err(error!(CustomErrorCode::InvalidCreator));
+    }
+    let (payment_token, receive_token) = match (creator_payment_token, receive_token_account) {
+        (Some(payment_token), Some(receive_token)) => (payment_token, receive_token),
+        _ => return Err(error!(CustomErrorCode::MissingPaymentAccountError)),
+    };
+    let reserved_price = if reserved_id.data_is_empty() {
+        None
+    } else {
+        let reserved: Account<ReservedId> = Account::try_from(reserved_id)?;
+        Some(reserved.price)
+    };
+    let cost = creation_cost(
+        id_length,
+        token_tracker_base.cost,
+        &token_tracker_base.vanity_costs,
+        reserved_price,
+    );
+    if cost > 0 {
+        transfers::transfer_legacy(
+            creator.to_account_info(),
+            payment_token.to_account_info(),
+            receive_token.to_account_info(),
+            token_program.to_account_info(),
+            cost,
+        )?;
     }
+    token_tracker_base.total_received = token_tracker_base.total_received.checked_add(cost).or_arith_error()?;
+    Ok(())
+}
 
+pub(crate) fn init_token_state(
+    token_state: &mut TokenState,
+    creator: Pubkey,
+    params: &CreateBondingTokenParams,
+) -> Result<()> {
     //state
-    token_state.state_index = token_tracker.index;
-    token_state.creator_address = ctx.accounts.creator.key();
+    token_state.creator_address = creator;
+    token_state.pending_creator_address = Pubkey::default();
+    token_state.delisted = false;
     token_state.next_halving = params.next_halving;
     token_state.emission_rate = params.emission_rate;
     token_state.bonding_cost = params.bonding_cost;
@@ -183,6 +227,9 @@ pub fn handle(ctx: Context<CreateBondingToken>, id: String, params: CreateBondin
     token_state.total_emissions = params.initial_reserve;
     token_state.quote_bonded = 0;
     token_state.total_topup = 0;
+    token_state.outstanding_liabilities = 0;
+    token_state.enforce_solvency = params.enforce_solvency;
+    token_state.mint_mode = params.mint_mode;
 
     token_state.mps = params.initial_reserve;
     token_state.avg_price = 0;
@@ -195,8 +242,60 @@ pub fn handle(ctx: Context<CreateBondingToken>, id: String, params: CreateBondin
     if params.runway_fee > token_state.fee_bps {
         return Err(error!(CustomErrorCode::RunwayFeeError));
     }
+    //leave room for the highest protocol fee the tracker can set later
+    if fees_exceed_total(params.runway_fee, token_state.fee_bps, MAX_PROTOCOL_FEE_BPS, PROTOCOL_FEE_BPS)? {
+        return Err(error!(CustomErrorCode::TotalFeeError));
+    }
     token_state.runway_fee = params.runway_fee;
-    
+    Ok(())
+}
+
+#[access_control(validate(params))]
+pub fn handle(ctx: Context<CreateBondingToken>, id: String, params: CreateBondingTokenParams) -> Result<()> {
+
+    let id_data = id_data(&id)?;
+
+    let token_state = &mut ctx.accounts.token_state;
+    let token_tracker = &mut ctx.accounts.token_tracker;
+    let token_tracker_base = &mut ctx.accounts.token_tracker_base;
+
+    charge_creation_cost(
+        &ctx.accounts.creator,
+        token_tracker_base,
+        &ctx.accounts.reserved_id,
+        &ctx.accounts.creator_payment_token,
+        &ctx.accounts.receive_token_account,
+        &ctx.accounts.token_program,
+        id.len(),
+    )?;
+    token_tracker_base.index = token_tracker_base.index.checked_add(1).or_arith_error()?;
+    token_tracker.token_state = token_state.key();
+    token_tracker.id = id_data;
+    token_tracker.index = token_tracker_base.index;
+    token_tracker.token_tracker_bump = *ctx.bumps.get("token_tracker").unwrap();
+
+    //
+    token_state.base_mint_address = ctx.accounts.base_mint.key();
+    token_state.base_mint_token_address = ctx.accounts.base_token_vault.key();
+    token_state.quote_mint_address = ctx.accounts.quote_mint.key();
+    token_state.base_decimals = ctx.accounts.base_mint.decimals;
+    token_state.quote_decimals = ctx.accounts.quote_mint.decimals;
+    token_state.base_token_program = *ctx.accounts.base_mint.to_account_info().owner;
+    token_state.quote_token_program = *ctx.accounts.quote_mint.to_account_info().owner;
+    validate_mint(&ctx.accounts.base_mint.to_account_info())?;
+    validate_mint(&ctx.accounts.quote_mint.to_account_info())?;
+    token_state.quote_reserve_token_address = ctx.accounts.quote_reserve_token_address.key();
+    token_state.quote_surplus_token_address = ctx.accounts.quote_surplus_token_address.key();
+    token_state.quote_runway_token_address = ctx.accounts.quote_runway_token_address.key();
+    if token_state.base_mint_address == token_state.quote_mint_address {
+        return Err(error!(CustomErrorCode::BaseAndQuoteMatch));
+    }
+    token_state.state_index = token_tracker.index;
+    init_token_state(token_state, ctx.accounts.creator.key(), &params)?;
+    //mint mode needs the base mint authority handed to the token state beforehand
+    if params.mint_mode && ctx.accounts.base_mint.mint_authority != COption::Some(token_state.key()) {
+        return Err(error!(CustomErrorCode::InvalidMintAuthority));
+    }
 
     //bumps
     token_state.id = id_data;
@@ -204,4 +303,4 @@ pub fn handle(ctx: Context<CreateBondingToken>, id: String, params: CreateBondin
     token_state.base_token_vault_bump = *ctx.bumps.get("base_token_vault").unwrap();
 
     Ok(())
-}
\ No newline at end of file
+}
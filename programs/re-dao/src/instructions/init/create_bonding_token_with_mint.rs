@@ -0,0 +1,203 @@
+use anchor_spl::associated_token;
+
+use crate::errors::*;
+use crate::instructions::create_bonding_token::{charge_creation_cost, id_data, init_token_state, validate};
+use crate::params::init::CreateBondingTokenParams;
+use crate::structs::TokenState;
+use crate::structs::{
+    token_tracker_base::TokenTrackerBase,
+    token_tracker::TokenTracker
+};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+use crate::utils::token_extensions::validate_mint;
+use {anchor_lang::{
+        prelude::{*},
+        solana_program::system_program,
+    },
+    anchor_spl::associated_token::AssociatedToken,
+    anchor_spl::token::{self, Token, ID},
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface}
+};
+//same as create_bonding_token, but the base mint is created as a PDA with the token state as mint authority and no freeze authority
+#[derive(Accounts)]
+#[instruction(id: String, params: CreateBondingTokenParams, base_decimals: u8)]
+pub struct CreateBondingTokenWithMint<'info> {
+    //tracker auth, or anyone paying the creation cost when the tracker is permissionless
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        init,
+        seeds = [
+            token_tracker_base.key().as_ref(), 
+            token_tracker_base.index
+            .checked_add(1)
+            .or_arith_error()?
+            .to_string().as_bytes()],
+        bump,
+        payer = creator,
+        space=132
+    )]
+    pub token_tracker: Box<Account<'info, TokenTracker>>,
+    #[account(
+        init,
+        seeds = [token_tracker_base.key().as_ref(), id.as_bytes()],
+        bump,
+        payer = creator,
+        space=2200
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    /// CHECK: reserved id PDA, only deserialized if it exists
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), b"reserved".as_ref(), id.as_bytes()],
+        bump,
+    )]
+    pub reserved_id: UncheckedAccount<'info>,
+    #[account(
+        init,
+        seeds = [token_state.key().as_ref(), b"base_mint".as_ref()],
+        bump,
+        payer = creator,
+        mint::decimals = base_decimals,
+        mint::authority = token_state,
+        mint::token_program = base_token_program,
+    )]
+    pub base_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(init,
+        token::mint = base_mint,
+        token::authority = token_state,
+        token::token_program = base_token_program,
+        seeds = [token_state.key().as_ref(), b"base_token".as_ref()],
+        bump,
+        payer = creator
+    )]
+    pub base_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    //receives the initial reserve when it is minted
+    #[account(
+        init,
+        associated_token::mint = base_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = base_token_program,
+        payer = creator
+    )]
+    pub creator_base_token: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        constraint = quote_mint.decimals >= 1,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        constraint = &quote_reserve_token_address.mint == quote_mint.to_account_info().key
+    )]
+    pub quote_reserve_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = &quote_surplus_token_address.mint == quote_mint.to_account_info().key
+    )]
+    pub quote_surplus_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = &quote_runway_token_address.mint == quote_mint.to_account_info().key
+    )]
+    pub quote_runway_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    //creation cost payment, only needed when the creator isn't the tracker auth
+    #[account(
+        mut,
+        constraint = creator_payment_token.mint == token_tracker_base.receive_mint,
+        constraint = &creator_payment_token.owner == creator.to_account_info().key
+    )]
+    pub creator_payment_token: Option<Box<Account<'info, token::TokenAccount>>>,
+    #[account(
+        mut,
+        address = token_tracker_base.receive_token_account
+    )]
+    pub receive_token_account: Option<Box<Account<'info, token::TokenAccount>>>,
+    //creation cost is paid in the tracker's legacy spl receive mint
+    #[account(address = ID)]
+    pub token_program: Program<'info, Token>,
+    //the base mint is created under this program, legacy spl token or token-2022
+    pub base_token_program: Interface<'info, TokenInterface>,
+    #[account(address = associated_token::ID)]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[access_control(validate(params))]
+pub fn handle(ctx: Context<CreateBondingTokenWithMint>, id: String, params: CreateBondingTokenParams, base_decimals: u8) -> Result<()> {
+    if base_decimals < 1 {
+        return Err(error!(CustomErrorCode::ZeroError));
+    }
+    let id_data = id_data(&id)?;
+
+    let token_state = &mut ctx.accounts.token_state;
+    let token_tracker = &mut ctx.accounts.token_tracker;
+    let token_tracker_base = &mut ctx.accounts.token_tracker_base;
+
+    charge_creation_cost(
+        &ctx.accounts.creator,
+        token_tracker_base,
+        &ctx.accounts.reserved_id,
+        &ctx.accounts.creator_payment_token,
+        &ctx.accounts.receive_token_account,
+        &ctx.accounts.token_program,
+        id.len(),
+    )?;
+    token_tracker_base.index = token_tracker_base.index.checked_add(1).or_arith_error()?;
+    token_tracker.token_state = token_state.key();
+    token_tracker.id = id_data;
+    token_tracker.index = token_tracker_base.index;
+    token_tracker.token_tracker_bump = *ctx.bumps.get("token_tracker").unwrap();
+
+    //
+    token_state.base_mint_address = ctx.accounts.base_mint.key();
+    token_state.base_mint_token_address = ctx.accounts.base_token_vault.key();
+    token_state.quote_mint_address = ctx.accounts.quote_mint.key();
+    token_state.base_decimals = base_decimals;
+    token_state.quote_decimals = ctx.accounts.quote_mint.decimals;
+    token_state.base_token_program = ctx.accounts.base_token_program.key();
+    token_state.quote_token_program = *ctx.accounts.quote_mint.to_account_info().owner;
+    validate_mint(&ctx.accounts.quote_mint.to_account_info())?;
+    token_state.quote_reserve_token_address = ctx.accounts.quote_reserve_token_address.key();
+    token_state.quote_surplus_token_address = ctx.accounts.quote_surplus_token_address.key();
+    token_state.quote_runway_token_address = ctx.accounts.quote_runway_token_address.key();
+    token_state.state_index = token_tracker.index;
+    init_token_state(token_state, ctx.accounts.creator.key(), &params)?;
+
+    //bumps
+    token_state.id = id_data;
+    token_state.token_state_bump = *ctx.bumps.get("token_state").unwrap();
+    token_state.base_token_vault_bump = *ctx.bumps.get("base_token_vault").unwrap();
+
+    //mint the initial reserve to the creator if an account was passed for it
+    if let Some(creator_base_token) = &ctx.accounts.creator_base_token {
+        if params.initial_reserve > 0 {
+            let id = token_state.id.as_ref();
+            let base_key = token_tracker_base.key();
+            let seeds = &[
+                base_key.as_ref(),
+                id.trim_ascii_whitespace(),
+                &[token_state.token_state_bump],
+            ];
+            transfers::mint_to_with_signer(
+                token_state.to_account_info(),
+                ctx.accounts.base_mint.to_account_info(),
+                creator_base_token.to_account_info(),
+                ctx.accounts.base_token_program.to_account_info(),
+                params.initial_reserve,
+                seeds,
+            )?;
+            //the minted reserve is already in the creator's hands, nothing left to allocate
+            token_state.allocated_reserve = params.initial_reserve;
+        }
+    }
+
+    Ok(())
+}
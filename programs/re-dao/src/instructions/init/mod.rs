@@ -1,7 +1,9 @@
 pub mod create_base_tracker;
 pub mod create_bonding_token;
+pub mod create_bonding_token_with_mint;
 pub mod create_vote_account;
 
 pub use create_base_tracker::*;
 pub use create_bonding_token::*;
+pub use create_bonding_token_with_mint::*;
 pub use create_vote_account::*;
\ No newline at end of file
@@ -1,7 +1,13 @@
+pub mod allocation;
 pub mod bonding;
 pub mod init;
+pub mod referral;
 pub mod update;
+pub mod voting;
 
+pub use allocation::*;
 pub use bonding::*;
 pub use init::*;
-pub use update::*;
\ No newline at end of file
+pub use referral::*;
+pub use update::*;
+pub use voting::*;
\ No newline at end of file
@@ -0,0 +1,74 @@
+use crate::errors::{CustomErrorCode, OrArithError};
+use crate::structs::{TokenTrackerBase, TokenState, Referral};
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    #[account(address = referral.referrer_address)]
+    pub referrer: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), b"referral".as_ref(), referrer.key().as_ref()],
+        bump = referral.referral_bump,
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+    #[account(
+        mut,
+        address = token_state.referral_vault_address
+    )]
+    pub referral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = referrer_quote_token.mint == token_state.quote_mint_address,
+        constraint = &referrer_quote_token.owner == referrer.to_account_info().key
+    )]
+    pub referrer_quote_token: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = token_state.quote_mint_address)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(address = token_state.quote_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle(ctx: Context<ClaimReferral>) -> Result<()> {
+    let base = &ctx.accounts.token_tracker_base;
+    let token_state = &ctx.accounts.token_state;
+    let referral = &mut ctx.accounts.referral;
+
+    let claimable = referral.total_earned.checked_sub(referral.total_claimed).or_arith_error()?;
+    if claimable == 0 {
+        return Err(error!(CustomErrorCode::NothingToClaimError));
+    }
+    let id = token_state.id.as_ref();
+    let base_key = base.key();
+    let seeds = &[
+        base_key.as_ref(),
+        id.trim_ascii_whitespace(),
+        &[token_state.token_state_bump],
+    ];
+    transfers::transfer_with_signer(
+        token_state.to_account_info(),
+        ctx.accounts.referral_vault.to_account_info(),
+        ctx.accounts.quote_mint.to_account_info(),
+        ctx.accounts.referrer_quote_token.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        claimable,
+        token_state.quote_decimals,
+        seeds,
+    )?;
+    referral.total_claimed = referral.total_earned;
+    Ok(())
+}
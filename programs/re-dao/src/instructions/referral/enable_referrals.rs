@@ -0,0 +1,55 @@
+use crate::errors::CustomErrorCode;
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+pub struct EnableReferrals<'info> {
+    #[account(
+        mut,
+        address = token_state.creator_address
+    )]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(address = token_state.quote_mint_address)]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(init,
+        token::mint = quote_mint,
+        token::authority = token_state,
+        token::token_program = token_program,
+        seeds = [token_state.key().as_ref(), b"referral_vault".as_ref()],
+        bump,
+        payer = creator
+    )]
+    pub referral_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = token_state.quote_token_program)]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<EnableReferrals>, referral_bps: u32) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    if referral_bps > token_state.fee_bps {
+        return Err(error!(CustomErrorCode::ReferralFeeError));
+    }
+    token_state.referral_vault_address = ctx.accounts.referral_vault.key();
+    token_state.referral_bps = referral_bps;
+    Ok(())
+}
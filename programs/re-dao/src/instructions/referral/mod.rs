@@ -0,0 +1,9 @@
+pub mod enable_referrals;
+pub mod update_referral_bps;
+pub mod register_referrer;
+pub mod claim_referral;
+
+pub use enable_referrals::*;
+pub use update_referral_bps::*;
+pub use register_referrer::*;
+pub use claim_referral::*;
@@ -0,0 +1,44 @@
+use crate::structs::{TokenTrackerBase, TokenState, Referral};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        init,
+        seeds = [token_state.key().as_ref(), b"referral".as_ref(), referrer.key().as_ref()],
+        bump,
+        payer = referrer,
+        space=100
+    )]
+    pub referral: Box<Account<'info, Referral>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+    referral.token_state_address = ctx.accounts.token_state.key();
+    referral.referrer_address = ctx.accounts.referrer.key();
+    referral.total_earned = 0;
+    referral.total_claimed = 0;
+    referral.referral_bump = *ctx.bumps.get("referral").unwrap();
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use crate::errors::CustomErrorCode;
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateReferralBps<'info> {
+    #[account(address = token_state.creator_address)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+//0 switches referrals off, already earned fees stay claimable
+pub fn handle(ctx: Context<UpdateReferralBps>, referral_bps: u32) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    if referral_bps > token_state.fee_bps {
+        return Err(error!(CustomErrorCode::ReferralFeeError));
+    }
+    token_state.referral_bps = referral_bps;
+    Ok(())
+}
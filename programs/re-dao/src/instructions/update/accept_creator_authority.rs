@@ -0,0 +1,29 @@
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct AcceptCreatorAuthority<'info> {
+    #[account(address = token_state.pending_creator_address)]
+    pub new_creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+pub fn handle(ctx: Context<AcceptCreatorAuthority>) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    token_state.creator_address = ctx.accounts.new_creator.key();
+    token_state.pending_creator_address = Pubkey::default();
+    Ok(())
+}
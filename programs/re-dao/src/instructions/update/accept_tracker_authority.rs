@@ -0,0 +1,24 @@
+use crate::structs::TokenTrackerBase;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct AcceptTrackerAuthority<'info> {
+    #[account(address = token_tracker_base.pending_auth_wallet)]
+    pub new_auth: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+}
+
+pub fn handle(ctx: Context<AcceptTrackerAuthority>) -> Result<()> {
+    let token_tracker_base = &mut ctx.accounts.token_tracker_base;
+    token_tracker_base.auth_wallet = ctx.accounts.new_auth.key();
+    token_tracker_base.pending_auth_wallet = Pubkey::default();
+    Ok(())
+}
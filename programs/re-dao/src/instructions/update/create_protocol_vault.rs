@@ -0,0 +1,44 @@
+use crate::structs::TokenTrackerBase;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+//one protocol vault per quote mint, owned by the tracker base
+#[derive(Accounts)]
+pub struct CreateProtocolVault<'info> {
+    #[account(
+        mut,
+        address = token_tracker_base.auth_wallet
+    )]
+    pub auth: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        constraint = quote_mint.to_account_info().owner == token_program.key,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(init,
+        token::mint = quote_mint,
+        token::authority = token_tracker_base,
+        token::token_program = token_program,
+        seeds = [token_tracker_base.key().as_ref(), b"protocol_vault".as_ref(), quote_mint.key().as_ref()],
+        bump,
+        payer = auth
+    )]
+    pub protocol_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(_ctx: Context<CreateProtocolVault>) -> Result<()> {
+    Ok(())
+}
@@ -2,8 +2,40 @@ pub mod freeze_contract;
 //pub mod update_bonding_period;
 pub mod update_creation_cost;
 pub mod update_launch_date;
+pub mod propose_tracker_authority;
+pub mod accept_tracker_authority;
+pub mod propose_creator_authority;
+pub mod accept_creator_authority;
+pub mod update_permissionless;
+pub mod update_listing;
+pub mod reserve_id;
+pub mod reprice_id;
+pub mod release_id;
+pub mod update_protocol_fee;
+pub mod create_protocol_vault;
+pub mod withdraw_protocol_fees;
+pub mod update_early_exit;
+pub mod update_period_vesting;
+pub mod update_rollover_multiplier;
+pub mod update_cancel_window;
 
 pub use freeze_contract::*;
 //pub use update_bonding_period::*;
 pub use update_creation_cost::*;
-pub use update_launch_date::*;
\ No newline at end of file
+pub use update_launch_date::*;
+pub use propose_tracker_authority::*;
+pub use accept_tracker_authority::*;
+pub use propose_creator_authority::*;
+pub use accept_creator_authority::*;
+pub use update_permissionless::*;
+pub use update_listing::*;
+pub use reserve_id::*;
+pub use reprice_id::*;
+pub use release_id::*;
+pub use update_protocol_fee::*;
+pub use create_protocol_vault::*;
+pub use withdraw_protocol_fees::*;
+pub use update_early_exit::*;
+pub use update_period_vesting::*;
+pub use update_rollover_multiplier::*;
+pub use update_cancel_window::*;
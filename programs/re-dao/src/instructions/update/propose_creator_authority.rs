@@ -0,0 +1,28 @@
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(new_creator: Pubkey)]
+pub struct ProposeCreatorAuthority<'info> {
+    #[account(address = token_state.creator_address)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+//proposing the default pubkey cancels a pending proposal
+pub fn handle(ctx: Context<ProposeCreatorAuthority>, new_creator: Pubkey) -> Result<()> {
+    ctx.accounts.token_state.pending_creator_address = new_creator;
+    Ok(())
+}
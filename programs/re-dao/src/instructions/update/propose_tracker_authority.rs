@@ -0,0 +1,23 @@
+use crate::structs::TokenTrackerBase;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(new_auth: Pubkey)]
+pub struct ProposeTrackerAuthority<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+}
+
+//proposing the default pubkey cancels a pending proposal
+pub fn handle(ctx: Context<ProposeTrackerAuthority>, new_auth: Pubkey) -> Result<()> {
+    ctx.accounts.token_tracker_base.pending_auth_wallet = new_auth;
+    Ok(())
+}
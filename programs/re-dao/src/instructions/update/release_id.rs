@@ -0,0 +1,36 @@
+use crate::structs::{TokenTrackerBase, ReservedId};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+//closing the reservation puts the id back on the length based tiers
+#[derive(Accounts)]
+#[instruction(id: String)]
+pub struct ReleaseId<'info> {
+    #[account(
+        mut,
+        address = token_tracker_base.auth_wallet
+    )]
+    pub auth: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), b"reserved".as_ref(), id.as_bytes()],
+        bump = reserved_id.reserved_id_bump,
+        close = auth
+    )]
+    pub reserved_id: Box<Account<'info, ReservedId>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(_ctx: Context<ReleaseId>, _id: String) -> Result<()> {
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::structs::{TokenTrackerBase, ReservedId};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(id: String)]
+pub struct RepriceId<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), b"reserved".as_ref(), id.as_bytes()],
+        bump = reserved_id.reserved_id_bump,
+    )]
+    pub reserved_id: Box<Account<'info, ReservedId>>,
+}
+
+pub fn handle(ctx: Context<RepriceId>, _id: String, price: u64) -> Result<()> {
+    ctx.accounts.reserved_id.price = price;
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::errors::CustomErrorCode;
+
+use crate::structs::{TokenTrackerBase, ReservedId};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction(id: String)]
+pub struct ReserveId<'info> {
+    #[account(
+        mut,
+        address = token_tracker_base.auth_wallet
+    )]
+    pub auth: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        init,
+        seeds = [token_tracker_base.key().as_ref(), b"reserved".as_ref(), id.as_bytes()],
+        bump,
+        payer = auth,
+        space=100
+    )]
+    pub reserved_id: Box<Account<'info, ReservedId>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<ReserveId>, id: String, price: u64) -> Result<()> {
+    let id_bytes = id.as_bytes();
+    if id_bytes.len() >= 20 {
+        return Err(error!(CustomErrorCode::InvalidIdLength));
+    }
+    let mut id_data = [b' '; 20];
+    id_data[..id_bytes.len()].copy_from_slice(id_bytes);
+
+    let reserved_id = &mut ctx.accounts.reserved_id;
+    reserved_id.token_tracker_base_address = ctx.accounts.token_tracker_base.key();
+    reserved_id.id = id_data;
+    reserved_id.price = price;
+    reserved_id.reserved_id_bump = *ctx.bumps.get("reserved_id").unwrap();
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::errors::CustomErrorCode;
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::program_option::COption,
+};
+use anchor_spl::token_interface::TokenAccount;
+
+#[derive(Accounts)]
+pub struct UpdateCancelWindow<'info> {
+    #[account(address = token_state.creator_address)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(address = token_state.quote_reserve_token_address)]
+    pub quote_reserve_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(address = token_state.quote_surplus_token_address)]
+    pub quote_surplus_token_address: Box<InterfaceAccount<'info, TokenAccount>>,
+}
+
+//refunds are signed by the token state, so it has to own or be a delegate of the reserve and surplus accounts
+pub fn handle(ctx: Context<UpdateCancelWindow>, cancel_window: u32) -> Result<()> {
+    let token_state_key = ctx.accounts.token_state.key();
+    if cancel_window > 0 {
+        for vault in [&ctx.accounts.quote_reserve_token_address, &ctx.accounts.quote_surplus_token_address] {
+            let delegated = vault.delegate == COption::Some(token_state_key);
+            if vault.owner != token_state_key && !delegated {
+                return Err(error!(CustomErrorCode::CancelAuthorityError));
+            }
+        }
+    }
+    ctx.accounts.token_state.cancel_window = cancel_window;
+    Ok(())
+}
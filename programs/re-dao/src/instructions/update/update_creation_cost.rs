@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::structs::TokenTrackerBase;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+#[derive(Accounts)]
+#[instruction(cost: u64, vanity_costs: [u64; 5])]
+pub struct UpdateCreationCost<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+}
+
+pub fn handle(ctx: Context<UpdateCreationCost>, cost: u64, vanity_costs: [u64; 5]) -> Result<()> {
+    let token_tracker_base = &mut ctx.accounts.token_tracker_base;
+    token_tracker_base.cost = cost;
+    token_tracker_base.vanity_costs = vanity_costs;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::errors::CustomErrorCode;
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateEarlyExit<'info> {
+    #[account(address = token_state.creator_address)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+//penalty is in fee_bps, applies to existing coupons as well
+pub fn handle(ctx: Context<UpdateEarlyExit>, enabled: bool, penalty: u32) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    if penalty > token_state.fee_bps {
+        return Err(error!(CustomErrorCode::EarlyExitPenaltyError));
+    }
+    token_state.early_exit_enabled = enabled;
+    token_state.early_exit_penalty = penalty;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+//curation stays with the tracker auth, even for permissionless token states
+#[derive(Accounts)]
+#[instruction(delisted: bool)]
+pub struct UpdateListing<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+pub fn handle(ctx: Context<UpdateListing>, delisted: bool) -> Result<()> {
+    ctx.accounts.token_state.delisted = delisted;
+    Ok(())
+}
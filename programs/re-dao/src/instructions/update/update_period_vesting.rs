@@ -0,0 +1,33 @@
+use crate::errors::CustomErrorCode;
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdatePeriodVesting<'info> {
+    #[account(address = token_state.creator_address)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+//only applies to new coupons, existing ones keep the schedule they were bonded with
+pub fn handle(ctx: Context<UpdatePeriodVesting>, vesting_linear: [bool; 10], cliff_fraction: [u32; 10]) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    if cliff_fraction.iter().any(|&fraction| fraction > token_state.fee_bps) {
+        return Err(error!(CustomErrorCode::InvalidVestingScheduleError));
+    }
+    token_state.period_vesting_linear = vesting_linear;
+    token_state.period_cliff_fraction = cliff_fraction;
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::structs::TokenTrackerBase;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(permissionless: bool)]
+pub struct UpdatePermissionless<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+}
+
+pub fn handle(ctx: Context<UpdatePermissionless>, permissionless: bool) -> Result<()> {
+    ctx.accounts.token_tracker_base.permissionless = permissionless;
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use crate::constants::MAX_PROTOCOL_FEE_BPS;
+use crate::errors::CustomErrorCode;
+use crate::structs::TokenTrackerBase;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(protocol_fee_bps: u32)]
+pub struct UpdateProtocolFee<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+}
+
+pub fn handle(ctx: Context<UpdateProtocolFee>, protocol_fee_bps: u32) -> Result<()> {
+    if protocol_fee_bps > MAX_PROTOCOL_FEE_BPS {
+        return Err(error!(CustomErrorCode::ProtocolFeeError));
+    }
+    ctx.accounts.token_tracker_base.protocol_fee_bps = protocol_fee_bps;
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use crate::constants::MAX_ROLLOVER_MULTIPLIER;
+use crate::errors::CustomErrorCode;
+use crate::structs::{TokenTrackerBase, TokenState};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct UpdateRolloverMultiplier<'info> {
+    #[account(address = token_state.creator_address)]
+    pub creator: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+//0 switches rollovers off, otherwise at least reward_bps so a rollover never shrinks the coupon, capped at 2x
+pub fn handle(ctx: Context<UpdateRolloverMultiplier>, rollover_multiplier: u32) -> Result<()> {
+    let token_state = &mut ctx.accounts.token_state;
+    if rollover_multiplier != 0 && (rollover_multiplier < token_state.reward_bps || rollover_multiplier > MAX_ROLLOVER_MULTIPLIER) {
+        return Err(error!(CustomErrorCode::RolloverMultiplierError));
+    }
+    token_state.rollover_multiplier = rollover_multiplier;
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use crate::errors::CustomErrorCode;
+use crate::structs::TokenTrackerBase;
+use crate::transfers::transfers;
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(address = token_tracker_base.auth_wallet)]
+    pub auth: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        constraint = quote_mint.to_account_info().owner == token_program.key,
+    )]
+    pub quote_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [token_tracker_base.key().as_ref(), b"protocol_vault".as_ref(), quote_mint.key().as_ref()],
+        bump,
+    )]
+    pub protocol_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = destination.mint == quote_mint.key()
+    )]
+    pub destination: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handle(ctx: Context<WithdrawProtocolFees>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(error!(CustomErrorCode::AmountIsZeroError));
+    }
+    let base = &ctx.accounts.token_tracker_base;
+    let id = base.id.as_ref();
+    let seeds = &[
+        id.trim_ascii_whitespace(),
+        &[base.token_tracker_bump],
+    ];
+    transfers::transfer_with_signer(
+        base.to_account_info(),
+        ctx.accounts.protocol_vault.to_account_info(),
+        ctx.accounts.quote_mint.to_account_info(),
+        ctx.accounts.destination.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        amount,
+        ctx.accounts.quote_mint.decimals,
+        seeds,
+    )?;
+    Ok(())
+}
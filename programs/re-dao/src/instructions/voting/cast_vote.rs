@@ -0,0 +1,83 @@
+use crate::calculations::calculations::{max_period_length, voting_power};
+use crate::errors::{CustomErrorCode, OrArithError};
+
+use crate::structs::{TokenTrackerBase, TokenState, BondVote, BondCoupon, VoteDelegation};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+//coupons to vote with are passed as writable remaining accounts
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct CastVote<'info> {
+    pub voter: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        constraint = &bond_vote.token_state_address == token_state.to_account_info().key
+    )]
+    pub bond_vote: Box<Account<'info, BondVote>>,
+    //required when the voter is not the owner
+    #[account(
+        seeds = [token_state.key().as_ref(), owner.as_ref(), b"delegation".as_ref()],
+        bump = vote_delegation.vote_delegation_bump,
+    )]
+    pub vote_delegation: Option<Account<'info, VoteDelegation>>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, CastVote<'info>>, owner: Pubkey) -> Result<()> {
+    let voter = ctx.accounts.voter.key();
+    if voter != owner {
+        match &ctx.accounts.vote_delegation {
+            Some(delegation) if delegation.delegate_address == voter => {}
+            _ => return Err(error!(CustomErrorCode::InvalidVoter)),
+        }
+    }
+
+    let clock = Clock::get()?;
+    let token_state = &ctx.accounts.token_state;
+    if token_state.voting_enabled_date <= clock.unix_timestamp {
+        return Err(error!(CustomErrorCode::VotingClosedError));
+    }
+
+    let max_lock = max_period_length(&token_state.period_lengths, &token_state.period_enabled);
+    let bond_vote_key = ctx.accounts.bond_vote.key();
+    let mut total_weight: u64 = 0;
+    for coupon_info in ctx.remaining_accounts.iter() {
+        let mut coupon: Account<'info, BondCoupon> = Account::try_from(coupon_info)?;
+        if coupon.token_state_address != token_state.key() || coupon.redeemer_address != owner {
+            return Err(error!(CustomErrorCode::InvalidCouponError));
+        }
+        //only unredeemed coupons that haven't voted yet carry weight, ve power is snapshotted at cast time
+        if coupon.is_redeemed || coupon.vote_cast != Pubkey::default() {
+            continue;
+        }
+        //partially claimed linear coupons only count what is still locked up
+        let remaining = coupon.tokens_to_redeem.checked_sub(coupon.claimed_so_far).or_arith_error()?;
+        let weight = voting_power(remaining, coupon.redemption_date, clock.unix_timestamp, max_lock)?;
+        if weight == 0 {
+            continue;
+        }
+        coupon.vote_cast = bond_vote_key;
+        coupon.vote_weight = weight;
+        //persist now so a coupon passed twice is skipped the second time
+        coupon.exit(ctx.program_id)?;
+        total_weight = total_weight.checked_add(weight).or_arith_error()?;
+    }
+    if total_weight == 0 {
+        return Err(error!(CustomErrorCode::NoVotingPowerError));
+    }
+
+    let bond_vote = &mut ctx.accounts.bond_vote;
+    bond_vote.total_votes = bond_vote.total_votes.checked_add(total_weight).or_arith_error()?;
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use crate::structs::{TokenTrackerBase, TokenState, VoteDelegation};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct DelegateVotes<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        init,
+        seeds = [token_state.key().as_ref(), owner.key().as_ref(), b"delegation".as_ref()],
+        bump,
+        payer = owner,
+        space=150
+    )]
+    pub vote_delegation: Box<Account<'info, VoteDelegation>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handle(ctx: Context<DelegateVotes>, delegate: Pubkey) -> Result<()> {
+    let vote_delegation = &mut ctx.accounts.vote_delegation;
+    vote_delegation.token_state_address = ctx.accounts.token_state.key();
+    vote_delegation.owner_address = ctx.accounts.owner.key();
+    vote_delegation.delegate_address = delegate;
+    vote_delegation.vote_delegation_bump = *ctx.bumps.get("vote_delegation").unwrap();
+    Ok(())
+}
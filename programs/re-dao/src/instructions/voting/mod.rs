@@ -0,0 +1,9 @@
+pub mod delegate_votes;
+pub mod revoke_delegation;
+pub mod cast_vote;
+pub mod voting_power;
+
+pub use delegate_votes::*;
+pub use revoke_delegation::*;
+pub use cast_vote::*;
+pub use voting_power::*;
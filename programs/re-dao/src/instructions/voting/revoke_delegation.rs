@@ -0,0 +1,41 @@
+use crate::structs::{TokenTrackerBase, TokenState, VoteDelegation};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::{
+    prelude::{*},
+    solana_program::system_program,
+};
+
+#[derive(Accounts)]
+#[instruction()]
+pub struct RevokeDelegation<'info> {
+    #[account(
+        mut,
+        address = vote_delegation.owner_address
+    )]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+    #[account(
+        mut,
+        seeds = [token_state.key().as_ref(), owner.key().as_ref(), b"delegation".as_ref()],
+        bump = vote_delegation.vote_delegation_bump,
+        close = owner
+    )]
+    pub vote_delegation: Box<Account<'info, VoteDelegation>>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handle(_ctx: Context<RevokeDelegation>) -> Result<()> {
+    //votes already cast by the delegate stay on their ballots, closing the account stops new ones
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use crate::calculations::calculations::{max_period_length, voting_power};
+use crate::errors::{CustomErrorCode, OrArithError};
+
+use crate::structs::{TokenTrackerBase, TokenState, BondCoupon};
+use crate::utils::ascii_trim::TrimAsciiWhitespace;
+
+use anchor_lang::prelude::*;
+
+//coupons to count are passed as remaining accounts in increasing coupon_count order, the total is returned as return data
+#[derive(Accounts)]
+#[instruction(user: Pubkey)]
+pub struct VotingPower<'info> {
+    #[account(
+        seeds = [token_tracker_base.id.as_ref().trim_ascii_whitespace()],
+        bump = token_tracker_base.token_tracker_bump,
+    )]
+    pub token_tracker_base: Account<'info, TokenTrackerBase>,
+    #[account(
+        seeds = [token_tracker_base.key().as_ref(), token_state.id.as_ref().trim_ascii_whitespace()],
+        bump = token_state.token_state_bump
+    )]
+    pub token_state: Box<Account<'info, TokenState>>,
+}
+
+pub fn handle<'info>(ctx: Context<'_, '_, '_, 'info, VotingPower<'info>>, user: Pubkey) -> Result<u64> {
+    let token_state = &ctx.accounts.token_state;
+    let now = Clock::get()?.unix_timestamp;
+    let max_lock = max_period_length(&token_state.period_lengths, &token_state.period_enabled);
+
+    let mut total_power: u64 = 0;
+    let mut last_coupon_count: u64 = 0;
+    for coupon_info in ctx.remaining_accounts.iter() {
+        let coupon: Account<'info, BondCoupon> = Account::try_from(coupon_info)?;
+        if coupon.token_state_address != token_state.key() || coupon.redeemer_address != user {
+            return Err(error!(CustomErrorCode::InvalidCouponError));
+        }
+        //strictly increasing counts rule out passing the same coupon twice, counts start at 1
+        if coupon.coupon_count <= last_coupon_count {
+            return Err(error!(CustomErrorCode::InvalidCouponError));
+        }
+        last_coupon_count = coupon.coupon_count;
+        if coupon.is_redeemed {
+            continue;
+        }
+        //partially claimed linear coupons only count what is still locked up
+        let remaining = coupon.tokens_to_redeem.checked_sub(coupon.claimed_so_far).or_arith_error()?;
+        let power = voting_power(remaining, coupon.redemption_date, now, max_lock)?;
+        total_power = total_power.checked_add(power).or_arith_error()?;
+    }
+    Ok(total_power)
+}
@@ -33,6 +33,15 @@ pub mod re_dao {
         instructions::create_bonding_token::handle(ctx, id, params)
     }
 
+    pub fn create_bonding_token_with_mint(
+        ctx: Context<CreateBondingTokenWithMint>,
+        id: String,
+        params: CreateBondingTokenParams,
+        base_decimals: u8
+    ) -> Result<()> {
+        instructions::create_bonding_token_with_mint::handle(ctx, id, params, base_decimals)
+    }
+
     pub fn create_vote_account(
         ctx: Context<CreateVoteAccount>,
         id: String
@@ -49,19 +58,266 @@ pub mod re_dao {
 
     pub fn bond(
         ctx: Context<Bond>,
-        id: String,
+        label: Option<String>,
         amount: u64,
         period_index: u8,
-        
+        beneficiary: Pubkey
     ) -> Result<()> {
-        instructions::bond::handle(ctx, id, amount, period_index)
+        instructions::bond::handle(ctx, label, amount, period_index, beneficiary)
     }
 
     pub fn redeem(
-        ctx: Context<RedeemCoupon>,
+        ctx: Context<RedeemCoupon>
+    ) -> Result<()> {
+        instructions::redeem_coupon::handle(ctx)
+    }
+
+    pub fn redeem_many<'info>(
+        ctx: Context<'_, '_, '_, 'info, RedeemMany<'info>>
+    ) -> Result<()> {
+        instructions::redeem_many::handle(ctx)
+    }
+
+    pub fn crank_redeem(
+        ctx: Context<CrankRedeem>
+    ) -> Result<()> {
+        instructions::crank_redeem::handle(ctx)
+    }
+
+    pub fn rollover_coupon(
+        ctx: Context<RolloverCoupon>,
+        period_index: u8
+    ) -> Result<()> {
+        instructions::rollover_coupon::handle(ctx, period_index)
+    }
+
+    pub fn cancel_bond(
+        ctx: Context<CancelBond>
+    ) -> Result<()> {
+        instructions::cancel_bond::handle(ctx)
+    }
+
+    pub fn transfer_coupon(
+        ctx: Context<TransferCoupon>,
+        new_owner: Pubkey
+    ) -> Result<()> {
+        instructions::transfer_coupon::handle(ctx, new_owner)
+    }
+
+    pub fn split_coupon(
+        ctx: Context<SplitCoupon>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::split_coupon::handle(ctx, amount)
+    }
+
+    pub fn merge_coupons(
+        ctx: Context<MergeCoupons>
+    ) -> Result<()> {
+        instructions::merge_coupons::handle(ctx)
+    }
+
+    pub fn exit_coupon(
+        ctx: Context<ExitCoupon>
+    ) -> Result<()> {
+        instructions::exit_coupon::handle(ctx)
+    }
+
+    pub fn solvency_ratio(
+        ctx: Context<SolvencyRatio>
+    ) -> Result<u64> {
+        instructions::solvency_ratio::handle(ctx)
+    }
+
+    pub fn create_allocation(
+        ctx: Context<CreateAllocation>,
+        beneficiary: Pubkey,
+        amount: u64,
+        start_date: i64,
+        cliff_length: i64,
+        vesting_length: i64
+    ) -> Result<()> {
+        instructions::create_allocation::handle(ctx, beneficiary, amount, start_date, cliff_length, vesting_length)
+    }
+
+    pub fn claim_allocation(
+        ctx: Context<ClaimAllocation>
+    ) -> Result<()> {
+        instructions::claim_allocation::handle(ctx)
+    }
+
+    pub fn enable_referrals(
+        ctx: Context<EnableReferrals>,
+        referral_bps: u32
+    ) -> Result<()> {
+        instructions::enable_referrals::handle(ctx, referral_bps)
+    }
+
+    pub fn update_referral_bps(
+        ctx: Context<UpdateReferralBps>,
+        referral_bps: u32
+    ) -> Result<()> {
+        instructions::update_referral_bps::handle(ctx, referral_bps)
+    }
+
+    pub fn update_early_exit(
+        ctx: Context<UpdateEarlyExit>,
+        enabled: bool,
+        penalty: u32
+    ) -> Result<()> {
+        instructions::update_early_exit::handle(ctx, enabled, penalty)
+    }
+
+    pub fn update_period_vesting(
+        ctx: Context<UpdatePeriodVesting>,
+        vesting_linear: [bool; 10],
+        cliff_fraction: [u32; 10]
+    ) -> Result<()> {
+        instructions::update_period_vesting::handle(ctx, vesting_linear, cliff_fraction)
+    }
+
+    pub fn update_rollover_multiplier(
+        ctx: Context<UpdateRolloverMultiplier>,
+        rollover_multiplier: u32
+    ) -> Result<()> {
+        instructions::update_rollover_multiplier::handle(ctx, rollover_multiplier)
+    }
+
+    pub fn update_cancel_window(
+        ctx: Context<UpdateCancelWindow>,
+        cancel_window: u32
+    ) -> Result<()> {
+        instructions::update_cancel_window::handle(ctx, cancel_window)
+    }
+
+    pub fn register_referrer(
+        ctx: Context<RegisterReferrer>
+    ) -> Result<()> {
+        instructions::register_referrer::handle(ctx)
+    }
+
+    pub fn claim_referral(
+        ctx: Context<ClaimReferral>
+    ) -> Result<()> {
+        instructions::claim_referral::handle(ctx)
+    }
+
+    pub fn propose_tracker_authority(
+        ctx: Context<ProposeTrackerAuthority>,
+        new_auth: Pubkey
+    ) -> Result<()> {
+        instructions::propose_tracker_authority::handle(ctx, new_auth)
+    }
+
+    pub fn accept_tracker_authority(
+        ctx: Context<AcceptTrackerAuthority>
+    ) -> Result<()> {
+        instructions::accept_tracker_authority::handle(ctx)
+    }
+
+    pub fn propose_creator_authority(
+        ctx: Context<ProposeCreatorAuthority>,
+        new_creator: Pubkey
+    ) -> Result<()> {
+        instructions::propose_creator_authority::handle(ctx, new_creator)
+    }
+
+    pub fn accept_creator_authority(
+        ctx: Context<AcceptCreatorAuthority>
+    ) -> Result<()> {
+        instructions::accept_creator_authority::handle(ctx)
+    }
+
+    pub fn update_permissionless(
+        ctx: Context<UpdatePermissionless>,
+        permissionless: bool
+    ) -> Result<()> {
+        instructions::update_permissionless::handle(ctx, permissionless)
+    }
+
+    pub fn update_listing(
+        ctx: Context<UpdateListing>,
+        delisted: bool
+    ) -> Result<()> {
+        instructions::update_listing::handle(ctx, delisted)
+    }
+
+    pub fn update_creation_cost(
+        ctx: Context<UpdateCreationCost>,
+        cost: u64,
+        vanity_costs: [u64; 5]
+    ) -> Result<()> {
+        instructions::update_creation_cost::handle(ctx, cost, vanity_costs)
+    }
+
+    pub fn reserve_id(
+        ctx: Context<ReserveId>,
+        id: String,
+        price: u64
+    ) -> Result<()> {
+        instructions::reserve_id::handle(ctx, id, price)
+    }
+
+    pub fn reprice_id(
+        ctx: Context<RepriceId>,
+        id: String,
+        price: u64
+    ) -> Result<()> {
+        instructions::reprice_id::handle(ctx, id, price)
+    }
+
+    pub fn release_id(
+        ctx: Context<ReleaseId>,
         id: String
     ) -> Result<()> {
-        instructions::redeem_coupon::handle(ctx, id)
+        instructions::release_id::handle(ctx, id)
+    }
+
+    pub fn update_protocol_fee(
+        ctx: Context<UpdateProtocolFee>,
+        protocol_fee_bps: u32
+    ) -> Result<()> {
+        instructions::update_protocol_fee::handle(ctx, protocol_fee_bps)
+    }
+
+    pub fn create_protocol_vault(
+        ctx: Context<CreateProtocolVault>
+    ) -> Result<()> {
+        instructions::create_protocol_vault::handle(ctx)
+    }
+
+    pub fn withdraw_protocol_fees(
+        ctx: Context<WithdrawProtocolFees>,
+        amount: u64
+    ) -> Result<()> {
+        instructions::withdraw_protocol_fees::handle(ctx, amount)
+    }
+
+    pub fn delegate_votes(
+        ctx: Context<DelegateVotes>,
+        delegate: Pubkey
+    ) -> Result<()> {
+        instructions::delegate_votes::handle(ctx, delegate)
+    }
+
+    pub fn revoke_delegation(
+        ctx: Context<RevokeDelegation>
+    ) -> Result<()> {
+        instructions::revoke_delegation::handle(ctx)
+    }
+
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVote<'info>>,
+        owner: Pubkey
+    ) -> Result<()> {
+        instructions::cast_vote::handle(ctx, owner)
+    }
+
+    pub fn voting_power<'info>(
+        ctx: Context<'_, '_, '_, 'info, VotingPower<'info>>,
+        user: Pubkey
+    ) -> Result<u64> {
+        instructions::voting_power::handle(ctx, user)
     }
 }
 
@@ -15,4 +15,6 @@ pub struct CreateBondingTokenParams {
     pub voting_enabled_date: i64,
     pub launch_date: i64,
     pub runway_fee: u32,
+    pub enforce_solvency: bool,
+    pub mint_mode: bool,
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+#[account]
+#[derive(Default)]
+//PDA = token_state address + "allocation" + beneficiary
+//slice of the initial reserve, vests linearly from start_date to end_date after the cliff
+pub struct Allocation {
+    pub token_state_address: Pubkey, //8 + 32 = 40
+    pub beneficiary_address: Pubkey, //72
+    pub total_amount: u64, //80
+    pub claimed_amount: u64, //88
+    pub start_date: i64, //96
+    pub cliff_date: i64, //104 //nothing is claimable before this
+    pub end_date: i64, //112 //fully vested
+    pub allocation_bump: u8, //113
+}
@@ -11,5 +11,20 @@ pub struct BondCoupon {
     pub redeemer_address: Pubkey,
     pub token_state_address: Pubkey,
     pub coupon_bump: u8,
-    pub id: [u8; 10] //1863 + 92 = 1955
+    pub label: [u8; 10], //optional display label, space padded
+    //voting
+    pub vote_cast: Pubkey, //bond vote the coupon weight was cast on, default if not voted
+    pub vote_weight: u64, //weight that was cast
+    pub bond_date: i64, //start of the lock, used for the early exit penalty
+    //vesting
+    pub vesting_linear: bool, //vests linearly from bond_date to redemption_date, cliff at maturity otherwise
+    pub cliff_date: i64, //nothing vests before this date
+    pub claimed_so_far: u64, //tokens already paid out by partial claims
+    //what bond added to the token state, reversed by cancel_bond. zero once split, merged or rolled over
+    pub bond_epoch: u32,
+    pub quote_bonded_amount: u64, //post fee quote added to quote_bonded
+    pub quote_reserve_amount: u64, //quote credited to the reserve
+    pub quote_surplus_amount: u64, //quote credited to the surplus
+    pub max_reward: u64, //added to mps
+    pub bonder_address: Pubkey, //wallet that paid the quote, the only one cancel_bond refunds
 }
\ No newline at end of file
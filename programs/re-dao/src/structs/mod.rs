@@ -3,9 +3,19 @@ pub mod token_tracker;
 pub mod token_state;
 pub mod bond_vote;
 pub mod bond_coupon;
+pub mod vote_delegation;
+pub mod reserved_id;
+pub mod allocation;
+pub mod referral;
+pub mod user_position;
 
 pub use token_tracker_base::*;
 pub use token_tracker::*;
 pub use token_state::*;
 pub use bond_vote::*;
 pub use bond_coupon::*;
+pub use vote_delegation::*;
+pub use reserved_id::*;
+pub use allocation::*;
+pub use referral::*;
+pub use user_position::*;
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+#[account]
+#[derive(Default)]
+//PDA = token_state address + "referral" + referrer
+//running totals of the runway fee share earned by a referrer
+pub struct Referral {
+    pub token_state_address: Pubkey, //8 + 32 = 40
+    pub referrer_address: Pubkey, //72
+    pub total_earned: u64, //80 //quote tokens credited to the referral vault
+    pub total_claimed: u64, //88
+    pub referral_bump: u8, //89
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+#[account]
+#[derive(Default)]
+//PDA = token_tracker_base address + "reserved" + id
+//reserved ids are sold at their own price instead of the length tier
+pub struct ReservedId {
+    pub token_tracker_base_address: Pubkey, //8 + 32 = 40
+    pub id: [u8; 20], //60
+    pub price: u64, //68
+    pub reserved_id_bump: u8, //69
+}
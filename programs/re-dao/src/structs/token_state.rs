@@ -6,6 +6,10 @@ pub struct TokenState {
     pub base_mint_address: Pubkey, //8 + 32 = 40
     pub base_mint_token_address: Pubkey, //72
     pub quote_mint_address: Pubkey, //104
+    pub base_decimals: u8,
+    pub quote_decimals: u8,
+    pub base_token_program: Pubkey, //legacy spl token or token-2022, taken from the mint owner
+    pub quote_token_program: Pubkey,
     //bumps
     pub token_state_bump: u8,
     pub base_token_vault_bump: u8,
@@ -16,7 +20,11 @@ pub struct TokenState {
     pub total_surplus_reserve: u64,
     pub quote_runway_token_address: Pubkey, //168
     pub total_runway_reserve: u64,
+    pub referral_vault_address: Pubkey, //holds referral fees until referrers claim them
+    pub total_referral_fees: u64,
+    pub total_protocol_fees: u64, //quote tokens sent to the tracker's protocol vault
     pub creator_address: Pubkey, //200
+    pub pending_creator_address: Pubkey, //proposed creator, has to accept before taking over
     //state
     pub genesis_emission_rate: u64,
     pub genesis_supply: u64,
@@ -28,13 +36,15 @@ pub struct TokenState {
     pub bond_coupon_count: u64, //488 //total amount of bond coupons issued
     
     pub total_topup: u64, //how many base tokens have been deposited into the base vault
+    pub outstanding_liabilities: u64, //base tokens issued in coupons that haven't been redeemed yet
     pub quote_bonded: u64, //total quote tokens that have been bonded
     pub total_emissions: u64, //supply emitted in total
     
     pub initial_reserve: u64, //how much has already been minted, offset for the current_epoch_emissions
+    pub allocated_reserve: u64, //how much of the initial reserve has been handed out as allocations
     pub mps: u64, //maximum potential supply if all bonding was at maximum period & rate
     pub avg_price: u64, //
-    pub floor_price: u64, //
+    pub floor_price: u64, //quote atoms per whole base token
     pub emission_rate: u64, //552 //supply emitted per bonding cost
     pub bonding_cost: u64, //616 //cost of emission rate
 
@@ -44,6 +54,8 @@ pub struct TokenState {
     pub period_multipliers: [u32; 10], //1576 //multiplier for bonding longer
     pub treasury_split: [u32; 10], //1576 //treasury split
     pub period_enabled: [bool; 10], //1586 //which periods are enabled
+    pub period_vesting_linear: [bool; 10], //coupons vest linearly over the period instead of at maturity
+    pub period_cliff_fraction: [u32; 10], //share of a linear period before anything vests, in fee_bps
     
     //bonding period totals
     //total quote bonded
@@ -54,8 +66,19 @@ pub struct TokenState {
     //controls
     pub voting_enabled_date: i64,
     pub updates_allowed: bool, //1715
+    pub delisted: bool, //set by the tracker auth, delisted tokens can't be bonded
+    pub enforce_solvency: bool, //refuse bonds the base vault can't cover
+    pub mint_mode: bool, //token state is the base mint authority and mints coupons on redemption
     pub launch_date: i64, //1779
     pub runway_fee: u32,
+    pub referral_bps: u32, //share of the runway fee paid to referrers
+    pub early_exit_enabled: bool, //coupons can be redeemed before maturity for a penalty
+    pub early_exit_penalty: u32, //penalty right after bonding, decays linearly to 0 at maturity
+    pub total_early_exits: u64, //coupons redeemed before maturity
+    pub total_forfeited: u64, //base tokens forfeited as early exit penalties, left in the vault or never minted
+    pub cancel_window: u32, //seconds after bonding a coupon can still be cancelled, 0 disables cancellations
+    pub total_cancelled: u64, //coupons cancelled inside the window
+    pub rollover_multiplier: u32, //bonus for rolling a matured coupon into a new period, in reward_bps, 0 disables rollovers
     pub fee_bps: u32,
     pub reward_bps: u32,
     //indexing
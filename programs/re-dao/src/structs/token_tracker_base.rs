@@ -10,11 +10,15 @@ pub struct TokenTrackerBase {
 
     //factory
     pub auth_wallet: Pubkey, //32 + 100 = 132
+    pub pending_auth_wallet: Pubkey, //proposed auth, has to accept before taking over
 
     //payment
     pub receive_mint: Pubkey, //32 + 132 = 164
     pub receive_token_account: Pubkey, //32 + 164 = 196
     pub total_received: u64,
     pub cost: u64, //64 + 196 = 260
+    pub vanity_costs: [u64; 5], //cost for ids of length 1 to 5, 0 falls back to cost
+    pub protocol_fee_bps: u32, //skimmed from every bond into the protocol vault, out of PROTOCOL_FEE_BPS, capped by MAX_PROTOCOL_FEE_BPS
     pub enabled: bool, //1 + 260 = 261
+    pub permissionless: bool, //anyone paying cost can create bonding tokens
 }
\ No newline at end of file
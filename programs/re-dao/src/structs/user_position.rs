@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::errors::OrArithError;
+#[account]
+#[derive(Default)]
+//PDA = token_state address + "position" + owner
+//running totals of a wallet's coupons, readable with one fetch instead of getProgramAccounts
+pub struct UserPosition {
+    pub token_state_address: Pubkey, //8 + 32 = 40
+    pub owner_address: Pubkey, //72
+    pub open_coupons: u64, //80 //coupons owned that haven't fully paid out
+    pub total_quote_bonded: u64, //88 //post fee quote bonded for this owner
+    pub total_base_pending: u64, //96 //base tokens still owed on open coupons
+    pub total_redeemed: u64, //104 //base tokens paid out
+    //hint only, earliest maturity seen when coupons were opened. not recomputed when a coupon closes,
+    //so it can point at a coupon that's already gone. 0 once nothing is open
+    pub next_maturity_hint: i64, //112
+    pub user_position_bump: u8, //113
+}
+
+impl UserPosition {
+    //bond, split, rollover or an incoming transfer
+    pub fn open_coupon(&mut self, tokens: u64, redemption_date: i64, now: i64) -> Result<()> {
+        self.open_coupons = self.open_coupons.checked_add(1).or_arith_error()?;
+        self.total_base_pending = self.total_base_pending.checked_add(tokens).or_arith_error()?;
+        if self.next_maturity_hint <= now || redemption_date < self.next_maturity_hint {
+            self.next_maturity_hint = redemption_date;
+        }
+        Ok(())
+    }
+
+    //coupon fully paid out or gone, tokens_left is what it still owed
+    pub fn close_coupon(&mut self, tokens_left: u64) -> Result<()> {
+        self.open_coupons = self.open_coupons.checked_sub(1).or_arith_error()?;
+        self.total_base_pending = self.total_base_pending.checked_sub(tokens_left).or_arith_error()?;
+        if self.open_coupons == 0 {
+            self.next_maturity_hint = 0;
+        }
+        Ok(())
+    }
+
+    pub fn redeem(&mut self, amount: u64) -> Result<()> {
+        self.total_base_pending = self.total_base_pending.checked_sub(amount).or_arith_error()?;
+        self.total_redeemed = self.total_redeemed.checked_add(amount).or_arith_error()?;
+        Ok(())
+    }
+}
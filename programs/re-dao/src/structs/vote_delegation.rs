@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+#[account]
+#[derive(Default)]
+//PDA = token_state address + owner address + "delegation"
+//closing the account revokes the delegation
+pub struct VoteDelegation {
+    pub token_state_address: Pubkey, //8 + 32 = 40
+    pub owner_address: Pubkey, //72
+    pub delegate_address: Pubkey, //104
+    pub vote_delegation_bump: u8, //105
+}
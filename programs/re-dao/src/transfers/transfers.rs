@@ -1,25 +1,107 @@
 use anchor_lang::prelude::*;
 
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_with_signer<'info>(
     authority: AccountInfo<'info>,
     from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
     to: AccountInfo<'info>,
     token_program: AccountInfo<'info>,
     amount: u64,
+    decimals: u8,
     seeds: &[&[u8]]
 ) -> Result<()> {
     let signer = &[&seeds[..]];
-    let cpi_accounts = anchor_spl::token::Transfer {
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
         from: from,
+        mint: mint,
         to: to,
         authority: authority,
     };
     let cpi_program = token_program;
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    return anchor_spl::token::transfer(cpi_ctx, amount);
+    return anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, decimals);
 }
 
 pub fn transfer<'info>(
+    authority: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let cpi_accounts = anchor_spl::token_interface::TransferChecked {
+        from: from,
+        mint: mint,
+        to: to,
+        authority: authority,
+    };
+    let cpi_program = token_program;
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    return anchor_spl::token_interface::transfer_checked(cpi_ctx, amount, decimals);
+}
+
+//wraps lamports straight into a native mint token account
+pub fn transfer_native<'info>(
+    authority: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = anchor_lang::system_program::Transfer {
+        from: authority,
+        to: to.clone(),
+    };
+    let cpi_ctx = CpiContext::new(system_program, cpi_accounts);
+    anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+    let cpi_accounts = anchor_spl::token_interface::SyncNative {
+        account: to,
+    };
+    let cpi_ctx = CpiContext::new(token_program, cpi_accounts);
+    anchor_spl::token_interface::sync_native(cpi_ctx)
+}
+
+//closes a token account owned by a pda, native mint accounts unwrap their balance into lamports
+pub fn close_with_signer<'info>(
+    authority: AccountInfo<'info>,
+    account: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    seeds: &[&[u8]]
+) -> Result<()> {
+    let signer = &[seeds];
+    let cpi_accounts = anchor_spl::token_interface::CloseAccount {
+        account,
+        destination,
+        authority,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+    anchor_spl::token_interface::close_account(cpi_ctx)
+}
+
+//pays quote tokens from the user's token account, or from the user's lamports when bonding native SOL
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_quote<'info>(
+    authority: AccountInfo<'info>,
+    from: Option<AccountInfo<'info>>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    match from {
+        Some(from) => transfer(authority, from, mint, to, token_program, amount, decimals),
+        None => transfer_native(authority, to, system_program, token_program, amount),
+    }
+}
+
+//creation cost is paid in the tracker's receive mint, which is always a legacy spl token
+pub fn transfer_legacy<'info>(
     authority: AccountInfo<'info>,
     from: AccountInfo<'info>,
     to: AccountInfo<'info>,
@@ -35,3 +117,41 @@ pub fn transfer<'info>(
     let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
     return anchor_spl::token::transfer(cpi_ctx, amount);
 }
+
+pub fn mint_to_with_signer<'info>(
+    authority: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    seeds: &[&[u8]]
+) -> Result<()> {
+    let signer = &[seeds];
+    let cpi_accounts = anchor_spl::token_interface::MintTo {
+        mint,
+        to,
+        authority,
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer);
+    anchor_spl::token_interface::mint_to(cpi_ctx, amount)
+}
+
+//pays out base tokens, minted when the token state holds the mint authority, otherwise from the base vault
+#[allow(clippy::too_many_arguments)]
+pub fn release_base_with_signer<'info>(
+    mint_mode: bool,
+    authority: AccountInfo<'info>,
+    base_mint: AccountInfo<'info>,
+    base_token_vault: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    decimals: u8,
+    seeds: &[&[u8]]
+) -> Result<()> {
+    if mint_mode {
+        mint_to_with_signer(authority, base_mint, to, token_program, amount, seeds)
+    } else {
+        transfer_with_signer(authority, base_token_vault, base_mint, to, token_program, amount, decimals, seeds)
+    }
+}
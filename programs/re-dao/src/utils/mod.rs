@@ -1 +1,2 @@
-pub mod ascii_trim;
\ No newline at end of file
+pub mod ascii_trim;
+pub mod token_extensions;
\ No newline at end of file
@@ -0,0 +1,58 @@
+use crate::errors::CustomErrorCode;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::TransferFeeConfig,
+        BaseStateWithExtensions,
+        ExtensionType,
+        StateWithExtensions,
+    },
+    state::Mint,
+};
+
+//wrapped SOL under either token program
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    mint == &anchor_spl::token::spl_token::native_mint::ID || mint == &spl_token_2022::native_mint::ID
+}
+
+//only extensions the vaults are known to work with are allowed, anything else is rejected,
+//transfer hooks included, so a dependency bump can't quietly let a new extension through
+pub fn validate_mint(mint: &AccountInfo) -> Result<()> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(());
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    //extensions this spl-token-2022 can't parse are unsupported as well
+    let extensions = mint_state
+        .get_extension_types()
+        .map_err(|_| error!(CustomErrorCode::UnsupportedMintExtensionError))?;
+    for extension in extensions {
+        match extension {
+            //transfer fees are credited net of the withheld fee, the rest don't affect transfers
+            ExtensionType::TransferFeeConfig
+            | ExtensionType::MintCloseAuthority
+            | ExtensionType::InterestBearingConfig => {}
+            _ => return Err(error!(CustomErrorCode::UnsupportedMintExtensionError)),
+        }
+    }
+    Ok(())
+}
+
+//fee withheld by the transfer fee extension when sending amount, 0 for legacy mints
+pub fn transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if mint.owner != &spl_token_2022::ID {
+        return Ok(0);
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(transfer_fee_config) => transfer_fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(CustomErrorCode::ArithmeticError)?,
+        Err(_) => 0,
+    };
+    Ok(fee)
+}
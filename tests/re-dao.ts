@@ -4,12 +4,16 @@ import { ReDao } from "../target/types/re_dao";
 import * as crypto from 'crypto';
 import {
   TOKEN_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  NATIVE_MINT,
   createMint,
   getAssociatedTokenAddressSync,
   createAssociatedTokenAccount,
   createAccount,
   getAccount,
-  mintTo
+  getMint,
+  mintTo,
+  approve
 } from '@solana/spl-token'
 import NodeWallet from "@coral-xyz/anchor/dist/cjs/nodewallet";
 import * as assert from 'assert'
@@ -23,6 +27,11 @@ describe("re-dao", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
   const program = anchor.workspace.ReDao as Program<ReDao>;
+  //one position per token state and coupon owner
+  const positionAddress = (tokenStateAddress: anchor.web3.PublicKey, owner: anchor.web3.PublicKey) => anchor.web3.PublicKey.findProgramAddressSync(
+    [tokenStateAddress.toBuffer(), Buffer.from("position"), owner.toBuffer()],
+    program.programId
+  )[0];
 
   const daoReserveWallet = Keypair.generate()
   let daoReserveTokenAddress = null;
@@ -136,6 +145,10 @@ describe("re-dao", () => {
       [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
       program.programId
     );
+    let [reservedIdAddress, reservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(ID)],
+      program.programId
+    );
     //token address for DAO wallet (receives WSOL)
     daoReserveTokenAddress = getAssociatedTokenAddressSync(quoteMint, daoReserveWallet.publicKey)
     try {
@@ -246,20 +259,26 @@ describe("re-dao", () => {
       updatesAllowed: updatesAllowed,
       votingEnabledDate: new anchor.BN(votingEnabled),
       launchDate: new anchor.BN(launchDate),
-      runwayFee: runwayFee
+      runwayFee: runwayFee,
+      enforceSolvency: false,
+      mintMode: false
     })
       .accounts({
         creator: provider.wallet.publicKey,
         tokenTrackerBase: tokenTrackerBaseAddress,
         tokenTracker: tokenTrackerAddress,
         tokenState: tokenStateAddress,
+        reservedId: reservedIdAddress,
         baseMint: baseMint,
         baseTokenVault: baseTokenAddress,
         quoteMint: quoteMint,
         quoteReserveTokenAddress: daoReserveTokenAddress,
         quoteSurplusTokenAddress: daoSurplusTokenAddress,
         quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        creatorPaymentToken: null,
+        receiveTokenAccount: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        baseTokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
         rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       }).rpc()
@@ -325,8 +344,12 @@ describe("re-dao", () => {
     assert.ok(tokenState.votingEnabledDate.toNumber() == votingEnabled)
     console.log("Token total topup:", tokenState.totalTopup.toString())
     assert.ok(tokenState.totalTopup.toString() == "0")
+    console.log("Token outstanding liabilities:", tokenState.outstandingLiabilities.toString())
+    assert.ok(tokenState.outstandingLiabilities.toString() == "0")
     console.log("Token mps:", tokenState.mps.toString())
     assert.ok(tokenState.mps.toString() == initialReserve.toString())
+    console.log("Token decimals:", tokenState.baseDecimals, tokenState.quoteDecimals)
+    assert.ok(tokenState.baseDecimals == 9 && tokenState.quoteDecimals == 9)
     console.log("Token floor price", tokenState.floorPrice.toNumber())
     assert.ok(tokenState.floorPrice.toNumber() == 0)
     console.log("Token avg price", tokenState.avgPrice.toNumber())
@@ -430,6 +453,7 @@ describe("re-dao", () => {
       tokenState: tokenStateAddress,
       baseTokenVault: baseTokenAddress,
       userBaseToken: baseMintTokenAddr,
+      baseMint: baseMint,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc()
@@ -454,8 +478,9 @@ describe("re-dao", () => {
     const periodIndex = 0;
     const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
     console.log(couponId)
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
     let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(couponId)],
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     let voteAccount1Id = "0|DAPE"
@@ -473,16 +498,20 @@ describe("re-dao", () => {
       [tokenStateAddress.toBuffer(), Buffer.from(voteAccount3Id)],
       program.programId
     );
-    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex).accounts({
+    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex, provider.wallet.publicKey).accounts({
       user: provider.wallet.publicKey,
       tokenTrackerBase: tokenTrackerBaseAddress,
       tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
       userQuoteToken: quoteMintTokenAddr,
       quoteRunwayTokenAddress: daoRunwayTokenAddress,
       quoteReserveTokenAddress: daoReserveTokenAddress,
       quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
       coupon: couponAddress,
-      bondVote: bondVoteAddress1,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc({ skipPreflight: true })
@@ -493,6 +522,9 @@ describe("re-dao", () => {
     //console.log(tokenState) 
     console.log("Total emissions: ", tokenState.totalEmissions.toString())
     assert.ok(tokenState.totalEmissions.toString() == "200001000000000000")
+    //total emissions - initial reserve
+    console.log("Outstanding liabilities: ", tokenState.outstandingLiabilities.toString())
+    assert.ok(tokenState.outstandingLiabilities.toString() == "1000000000000")
     console.log("Mps: ", tokenState.mps.toString())
     assert.ok(tokenState.mps.toString() == "200001088000000000")
     console.log("Quote bonded: ", tokenState.quoteBonded.toNumber())
@@ -531,12 +563,12 @@ describe("re-dao", () => {
     console.log("Dao runway amount", runway.amount.toString())
     assert.ok(runway.amount.toString() == (1000000).toString())
 
-    //vote
+    //vote - bonding doesn't vote, coupon power is cast with castVote
     const voteAccount1 = await program.account.bondVote.fetch(bondVoteAddress1)
     assert.ok(String.fromCharCode(...voteAccount1.id).trim() === voteAccount1Id, "Vote account 1 id mismatch");
     assert.ok(voteAccount1.tokenStateAddress.toBase58() == tokenStateAddress.toBase58(), "Vote account 1 token state address mismatch.")
     console.log("Vote account 1 votes", voteAccount1.totalVotes.toNumber())
-    assert.ok(voteAccount1.totalVotes.toNumber() == bondingAmount, "Vote account 1 total votes mismatch.")
+    assert.ok(voteAccount1.totalVotes.toNumber() == 0, "Vote account 1 total votes mismatch.")
 
     const voteAccount2 = await program.account.bondVote.fetch(bondVoteAddress2)
     assert.ok(String.fromCharCode(...voteAccount2.id).trim() === voteAccount2Id, "Vote account 2 id mismatch");
@@ -568,8 +600,9 @@ describe("re-dao", () => {
     console.log("zoink")
     const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
     console.log(couponId)
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
     let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(couponId)],
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     let voteAccount1Id = "0|DAPE"
@@ -587,16 +620,20 @@ describe("re-dao", () => {
       [tokenStateAddress.toBuffer(), Buffer.from(voteAccount3Id)],
       program.programId
     );
-    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex).accounts({
+    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex, provider.wallet.publicKey).accounts({
       user: provider.wallet.publicKey,
       tokenTrackerBase: tokenTrackerBaseAddress,
       tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
       userQuoteToken: quoteMintTokenAddr,
       quoteRunwayTokenAddress: daoRunwayTokenAddress,
       quoteReserveTokenAddress: daoReserveTokenAddress,
       quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
       coupon: couponAddress,
-      bondVote: bondVoteAddress1,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc({ skipPreflight: true })
@@ -607,6 +644,9 @@ describe("re-dao", () => {
     //console.log(tokenState) 
     console.log("Total emissions: ", tokenState.totalEmissions.toString())
     assert.ok(tokenState.totalEmissions.toString() == "200002033000000000")
+    //total emissions - initial reserve
+    console.log("Outstanding liabilities: ", tokenState.outstandingLiabilities.toString())
+    assert.ok(tokenState.outstandingLiabilities.toString() == "2033000000000")
     console.log("Mps: ", tokenState.mps.toString())
     assert.ok(tokenState.mps.toString() == "200002176000000000")
     console.log("Quote bonded: ", tokenState.quoteBonded.toNumber())
@@ -644,12 +684,12 @@ describe("re-dao", () => {
     console.log("Dao runway amount", runway.amount.toString())
     assert.ok(runway.amount.toString() == (2000000).toString())
 
-    //vote
+    //vote - bonding doesn't vote, coupon power is cast with castVote
     const voteAccount1 = await program.account.bondVote.fetch(bondVoteAddress1)
     assert.ok(String.fromCharCode(...voteAccount1.id).trim() === voteAccount1Id, "Vote account 1 id mismatch");
     assert.ok(voteAccount1.tokenStateAddress.toBase58() == tokenStateAddress.toBase58(), "Vote account 1 token state address mismatch.")
     console.log("Vote account 1 votes", voteAccount1.totalVotes.toNumber())
-    assert.ok(voteAccount1.totalVotes.toNumber() == bondingAmount * 2, "Vote account 1 total votes mismatch.")
+    assert.ok(voteAccount1.totalVotes.toNumber() == 0, "Vote account 1 total votes mismatch.")
 
     const voteAccount2 = await program.account.bondVote.fetch(bondVoteAddress2)
     assert.ok(String.fromCharCode(...voteAccount2.id).trim() === voteAccount2Id, "Vote account 2 id mismatch");
@@ -680,8 +720,9 @@ describe("re-dao", () => {
     const periodIndex = 2;
     const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
     console.log(couponId)
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
     let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-      [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(couponId)],
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
       program.programId
     );
     let voteAccount1Id = "0|DAPE"
@@ -699,17 +740,21 @@ describe("re-dao", () => {
       [tokenStateAddress.toBuffer(), Buffer.from(voteAccount3Id)],
       program.programId
     );
-    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex).accounts({
+    let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex, provider.wallet.publicKey).accounts({
       user: provider.wallet.publicKey,
       tokenTrackerBase: tokenTrackerBaseAddress,
       tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
 
       userQuoteToken: quoteMintTokenAddr,
       quoteRunwayTokenAddress: daoRunwayTokenAddress,
       quoteReserveTokenAddress: daoReserveTokenAddress,
       quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
       coupon: couponAddress,
-      bondVote: null,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
       tokenProgram: TOKEN_PROGRAM_ID,
       systemProgram: anchor.web3.SystemProgram.programId,
     }).rpc({skipPreflight: true})
@@ -721,6 +766,9 @@ describe("re-dao", () => {
     console.log("Epoch: ", tokenState.epochCount.toString())
     console.log("Total emissions: ", tokenState.totalEmissions.toString())
     assert.ok(tokenState.totalEmissions.toString() == "200003121000000000")
+    //total emissions - initial reserve
+    console.log("Outstanding liabilities: ", tokenState.outstandingLiabilities.toString())
+    assert.ok(tokenState.outstandingLiabilities.toString() == "3121000000000")
     console.log("Mps: ", tokenState.mps.toString())
     assert.ok(tokenState.mps.toString() == "200003264000000000")
     console.log("Quote bonded: ", tokenState.quoteBonded.toNumber())
@@ -758,12 +806,12 @@ describe("re-dao", () => {
     console.log("Dao runway amount", runway.amount.toString())
     assert.ok(runway.amount.toString() == (3000000).toString())
 
-    //vote
+    //vote - bonding doesn't vote, coupon power is cast with castVote
     const voteAccount1 = await program.account.bondVote.fetch(bondVoteAddress1)
     assert.ok(String.fromCharCode(...voteAccount1.id).trim() === voteAccount1Id, "Vote account 1 id mismatch");
     assert.ok(voteAccount1.tokenStateAddress.toBase58() == tokenStateAddress.toBase58(), "Vote account 1 token state address mismatch.")
     console.log("Vote account 1 votes", voteAccount1.totalVotes.toNumber())
-    assert.ok(voteAccount1.totalVotes.toNumber() == 20000000, "Vote account 1 total votes mismatch.")
+    assert.ok(voteAccount1.totalVotes.toNumber() == 0, "Vote account 1 total votes mismatch.")
 
     const voteAccount2 = await program.account.bondVote.fetch(bondVoteAddress2)
     assert.ok(String.fromCharCode(...voteAccount2.id).trim() === voteAccount2Id, "Vote account 2 id mismatch");
@@ -777,9 +825,7 @@ describe("re-dao", () => {
     console.log("Vote account 3 votes", voteAccount3.totalVotes.toNumber())
     assert.ok(voteAccount3.totalVotes.toNumber() == 0, "Vote account 3 total votes mismatch.")
   });
-  it("Redeem coupons", async () => {
-    //sleep
-    await new Promise(r => setTimeout(r, 15000));
+  it("Solvency ratio", async () => {
     let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
       [Buffer.from(TRACKER_ID)],
       program.programId
@@ -788,59 +834,1844 @@ describe("re-dao", () => {
       [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
       program.programId
     );
-    //fetch coupons
-    console.log(provider.wallet.publicKey.toBase58())
-    let coupons = await program.account.bondCoupon.all([
-      { 
-        memcmp: {
-          offset: 8 + 8 + 1 + 1 + 8 + 8,
-          bytes: provider.wallet.publicKey.toBase58()
-        }
-      },
-      { 
-        memcmp: {
-          offset: 8 + 8 + 1 + 1 + 8 + 8 + 32,
-          bytes: tokenStateAddress.toBase58()
-        }
-      }
-    ])
+    const tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    const ratio = await program.methods.solvencyRatio().accounts({
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).view()
+    //(topup - redeemed) * fee bps / liabilities
+    const expected = tokenState.totalTopup.sub(tokenState.totalRedeemed).mul(new anchor.BN(tokenState.feeBps)).div(tokenState.outstandingLiabilities)
+    console.log("Solvency ratio", ratio.toString())
+    assert.ok(ratio.toString() == expected.toString(), "Solvency ratio mismatch.")
+  });
+  it("Delegate and revoke votes", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [voteDelegationAddress, voteDelegationBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from("delegation")],
+      program.programId
+    );
+    const delegate = Keypair.generate()
+    let tx = await program.methods.delegateVotes(delegate.publicKey).accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      voteDelegation: voteDelegationAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+    console.log("Delegate tx", tx)
+    const voteDelegation = await program.account.voteDelegation.fetch(voteDelegationAddress)
+    assert.ok(voteDelegation.ownerAddress.toBase58() == provider.wallet.publicKey.toBase58(), "Delegation owner mismatch.")
+    assert.ok(voteDelegation.delegateAddress.toBase58() == delegate.publicKey.toBase58(), "Delegation delegate mismatch.")
+    assert.ok(voteDelegation.tokenStateAddress.toBase58() == tokenStateAddress.toBase58(), "Delegation token state mismatch.")
 
-    //console.log(coupons)
-    for (let index = 0; index < coupons.length; index++) {
-      const coupon = coupons[index];
-      let couponId = String.fromCharCode(...coupon.account.id).trim()
-      console.log(`State address: ${coupon.account.tokenStateAddress.toBase58()} Redeemer address: ${coupon.account.redeemerAddress.toBase58()} Id: ${couponId}`)
-      let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
-        [Buffer.from(TRACKER_ID)],
-        program.programId
-      );
-      let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
-        [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
-        program.programId
-      );
-      let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-        [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(String.fromCharCode(...coupon.account.id).trim())],
-        program.programId
-      );
-      let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
-        [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
-        program.programId
-      );
-      let tx = await program.methods.redeem(couponId).accounts({
-        user: provider.wallet.publicKey,
+    let txRevoke = await program.methods.revokeDelegation().accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      voteDelegation: voteDelegationAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    console.log("Revoke tx", txRevoke)
+    const closed = await provider.connection.getAccountInfo(voteDelegationAddress)
+    assert.ok(closed == null, "Delegation account not closed.")
+  });
+  it("Rotate tracker and creator authority", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    const newAuth = Keypair.generate()
+    //hand the tracker over and back again
+    await program.methods.proposeTrackerAuthority(newAuth.publicKey).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+    let trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress)
+    assert.ok(trackerBase.pendingAuthWallet.toBase58() == newAuth.publicKey.toBase58(), "Pending auth mismatch.")
+    await program.methods.acceptTrackerAuthority().accounts({
+      newAuth: newAuth.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).signers([newAuth]).rpc()
+    trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress)
+    assert.ok(trackerBase.authWallet.toBase58() == newAuth.publicKey.toBase58(), "Auth not rotated.")
+    await program.methods.proposeTrackerAuthority(provider.wallet.publicKey).accounts({
+      auth: newAuth.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).signers([newAuth]).rpc()
+    await program.methods.acceptTrackerAuthority().accounts({
+      newAuth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+    trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress)
+    assert.ok(trackerBase.authWallet.toBase58() == provider.wallet.publicKey.toBase58(), "Auth not rotated back.")
+    assert.ok(trackerBase.pendingAuthWallet.toBase58() == anchor.web3.PublicKey.default.toBase58(), "Pending auth not cleared.")
+
+    //same flow for the token state creator
+    await program.methods.proposeCreatorAuthority(newAuth.publicKey).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+    await program.methods.acceptCreatorAuthority().accounts({
+      newCreator: newAuth.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).signers([newAuth]).rpc()
+    let tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.creatorAddress.toBase58() == newAuth.publicKey.toBase58(), "Creator not rotated.")
+    await program.methods.proposeCreatorAuthority(provider.wallet.publicKey).accounts({
+      creator: newAuth.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).signers([newAuth]).rpc()
+    await program.methods.acceptCreatorAuthority().accounts({
+      newCreator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+    tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.creatorAddress.toBase58() == provider.wallet.publicKey.toBase58(), "Creator not rotated back.")
+  });
+  it("Toggle launchpad mode and listing", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    await program.methods.updatePermissionless(true).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+    let trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress)
+    assert.ok(trackerBase.permissionless == true, "Tracker not permissionless.")
+    assert.ok(trackerBase.receiveMint.toBase58() == quoteMint.toBase58(), "Receive mint mismatch.")
+    await program.methods.updatePermissionless(false).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+    trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress)
+    assert.ok(trackerBase.permissionless == false, "Tracker still permissionless.")
+
+    await program.methods.updateListing(true).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+    let tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.delisted == true, "Token not delisted.")
+    await program.methods.updateListing(false).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+    tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.delisted == false, "Token still delisted.")
+  });
+  it("Vanity id pricing and reservations", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    const cost = new anchor.BN(15).mul(new anchor.BN(LAMPORTS_PER_SOL))
+    const vanityCosts = [1000, 500, 100, 50, 20].map((sol) => new anchor.BN(sol).mul(new anchor.BN(LAMPORTS_PER_SOL)))
+    await program.methods.updateCreationCost(cost, vanityCosts).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+    const trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress)
+    assert.ok(trackerBase.cost.toString() == cost.toString(), "Creation cost mismatch.")
+    assert.ok(
+      trackerBase.vanityCosts.every((value, index) => value.toString() === vanityCosts[index].toString()),
+      "Vanity costs mismatch."
+    );
+
+    const reservedId = "gm"
+    let [reservedIdAddress, reservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(reservedId)],
+      program.programId
+    );
+    const price = new anchor.BN(5000).mul(new anchor.BN(LAMPORTS_PER_SOL))
+    await program.methods.reserveId(reservedId, price).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      reservedId: reservedIdAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+    let reserved = await program.account.reservedId.fetch(reservedIdAddress)
+    assert.ok(String.fromCharCode(...reserved.id).trim() === reservedId, "Reserved id mismatch.")
+    assert.ok(reserved.price.toString() == price.toString(), "Reserved price mismatch.")
+
+    const newPrice = new anchor.BN(2500).mul(new anchor.BN(LAMPORTS_PER_SOL))
+    await program.methods.repriceId(reservedId, newPrice).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      reservedId: reservedIdAddress,
+    }).rpc()
+    reserved = await program.account.reservedId.fetch(reservedIdAddress)
+    assert.ok(reserved.price.toString() == newPrice.toString(), "Reserved price not updated.")
+
+    await program.methods.releaseId(reservedId).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      reservedId: reservedIdAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const released = await provider.connection.getAccountInfo(reservedIdAddress)
+    assert.ok(released == null, "Reserved id not released.")
+  });
+  it("Create bonding token with mint", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    const trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress);
+    const index = trackerBase.index.toNumber() + 1
+    const mintId = crypto.randomBytes(20).toString('hex').slice(0, 6);
+    let [tokenTrackerAddress, tokenTrackerBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(index.toString())],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(mintId)],
+      program.programId
+    );
+    let [reservedIdAddress, reservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(mintId)],
+      program.programId
+    );
+    let [baseMintAddress, baseMintBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_mint")],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    const creatorBaseToken = getAssociatedTokenAddressSync(baseMintAddress, provider.wallet.publicKey)
+    const initialReserve = new anchor.BN(1_000_000).mul(new anchor.BN(LAMPORTS_PER_SOL))
+
+    await program.methods.createBondingTokenWithMint(mintId, {
+      nextHalving: new anchor.BN(1_000_000_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+      emissionRate: new anchor.BN(1_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+      bondingCost: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+      initialReserve: initialReserve,
+      periodLengths: [1, 7, 14, 0, 0, 0, 0, 0, 0, 0].map((length) => new anchor.BN(length)),
+      periodMultipliers: [10000, 10330, 10880, 0, 0, 0, 0, 0, 0, 0],
+      treasurySplit: [1000, 3300, 8800, 0, 0, 0, 0, 0, 0, 0],
+      periodEnabled: [true, true, true, false, false, false, false, false, false, false],
+      updatesAllowed: true,
+      votingEnabledDate: new anchor.BN(Date.parse("25 Oct 2024 15:00:00 GMT") / 1000),
+      launchDate: new anchor.BN(Date.parse("24 Oct 2021 15:00:00 GMT") / 1000),
+      runwayFee: 10000,
+      enforceSolvency: false,
+      mintMode: true
+    }, 6)
+      .accounts({
+        creator: provider.wallet.publicKey,
         tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenTracker: tokenTrackerAddress,
         tokenState: tokenStateAddress,
-        userBaseToken: baseMintTokenAddr,
+        reservedId: reservedIdAddress,
+        baseMint: baseMintAddress,
         baseTokenVault: baseTokenAddress,
-        coupon: couponAddress,
+        creatorBaseToken: creatorBaseToken,
+        quoteMint: quoteMint,
+        quoteReserveTokenAddress: daoReserveTokenAddress,
+        quoteSurplusTokenAddress: daoSurplusTokenAddress,
+        quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        creatorPaymentToken: null,
+        receiveTokenAccount: null,
         tokenProgram: TOKEN_PROGRAM_ID,
+        baseTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
       }).rpc()
-      console.log("Claim tx", tx)
-      const couponRes = await program.account.bondCoupon.fetch(couponAddress)
-      console.log("Coupon claimed:", couponRes.isRedeemed)
-      assert.ok(couponRes.isRedeemed == true)
-    }
+    const tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.baseMintAddress.toBase58() == baseMintAddress.toBase58(), "Base mint not set.")
+    assert.ok(tokenState.mintMode, "Mint mode not set.")
+    //base and quote decimals don't have to match
+    assert.ok(tokenState.baseDecimals == 6 && tokenState.quoteDecimals == 9, "Decimals mismatch.")
+    const mintInfo = await getMint(provider.connection, baseMintAddress)
+    assert.ok(mintInfo.mintAuthority.toBase58() == tokenStateAddress.toBase58(), "Mint authority mismatch.")
+    assert.ok(mintInfo.freezeAuthority == null, "Freeze authority should be empty.")
+    const creatorAccount = await getAccount(provider.connection, creatorBaseToken)
+    assert.ok(creatorAccount.amount.toString() == initialReserve.toString(), "Initial reserve not minted.")
+
+    //floor price is quote atoms per whole base token, scaled by the base decimals only
+    const couponCount = tokenState.bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(null, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const bonded = await program.account.tokenState.fetch(tokenStateAddress)
+    const expectedFloor = bonded.quoteBonded.mul(new anchor.BN(10).pow(new anchor.BN(6))).div(bonded.mps)
+    assert.ok(bonded.floorPrice.gtn(0), "Floor price not set.")
+    assert.ok(bonded.floorPrice.eq(expectedFloor), "Floor price not scaled by the base decimals.")
+  });
+  it("Bond native SOL", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    const trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress);
+    const index = trackerBase.index.toNumber() + 1
+    const solId = crypto.randomBytes(20).toString('hex').slice(0, 6);
+    let [tokenTrackerAddress, tokenTrackerBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(index.toString())],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(solId)],
+      program.programId
+    );
+    let [reservedIdAddress, reservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(solId)],
+      program.programId
+    );
+    let [baseMintAddress, baseMintBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_mint")],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    //wSOL vaults for the dao wallets
+    const payer = (provider.wallet as NodeWallet).payer
+    const solReserve = await createAssociatedTokenAccount(provider.connection, payer, NATIVE_MINT, daoReserveWallet.publicKey)
+    const solSurplus = await createAssociatedTokenAccount(provider.connection, payer, NATIVE_MINT, daoSurplusWallet.publicKey)
+    const solRunway = await createAssociatedTokenAccount(provider.connection, payer, NATIVE_MINT, daoRunwayWallet.publicKey)
+
+    await program.methods.createBondingTokenWithMint(solId, {
+      nextHalving: new anchor.BN(1_000_000_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+      emissionRate: new anchor.BN(1_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+      bondingCost: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+      initialReserve: new anchor.BN(0),
+      periodLengths: [1, 7, 14, 0, 0, 0, 0, 0, 0, 0].map((length) => new anchor.BN(length)),
+      periodMultipliers: [10000, 10330, 10880, 0, 0, 0, 0, 0, 0, 0],
+      treasurySplit: [1000, 3300, 8800, 0, 0, 0, 0, 0, 0, 0],
+      periodEnabled: [true, true, true, false, false, false, false, false, false, false],
+      updatesAllowed: true,
+      votingEnabledDate: new anchor.BN(Date.parse("25 Oct 2024 15:00:00 GMT") / 1000),
+      launchDate: new anchor.BN(Date.parse("24 Oct 2021 15:00:00 GMT") / 1000),
+      runwayFee: 10000,
+      enforceSolvency: false,
+      mintMode: true
+    }, 9)
+      .accounts({
+        creator: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenTracker: tokenTrackerAddress,
+        tokenState: tokenStateAddress,
+        reservedId: reservedIdAddress,
+        baseMint: baseMintAddress,
+        baseTokenVault: baseTokenAddress,
+        creatorBaseToken: null,
+        quoteMint: NATIVE_MINT,
+        quoteReserveTokenAddress: solReserve,
+        quoteSurplusTokenAddress: solSurplus,
+        quoteRunwayTokenAddress: solRunway,
+        creatorPaymentToken: null,
+        receiveTokenAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        baseTokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      }).rpc()
+
+    const bondingAmount = 0.01 * LAMPORTS_PER_SOL
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    //no wSOL account, lamports come straight from the wallet
+    await program.methods.bond(couponId, new anchor.BN(bondingAmount), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: null,
+      quoteRunwayTokenAddress: solRunway,
+      quoteReserveTokenAddress: solReserve,
+      quoteSurplusTokenAddress: solSurplus,
+      quoteMint: NATIVE_MINT,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    const credited = tokenState.totalReserve.add(tokenState.totalSurplusReserve).add(tokenState.totalRunwayReserve)
+    assert.ok(credited.toNumber() == bondingAmount, "Native bond not credited.")
+    const synced = (await getAccount(provider.connection, solReserve)).amount
+      + (await getAccount(provider.connection, solSurplus)).amount
+      + (await getAccount(provider.connection, solRunway)).amount
+    assert.ok(synced.toString() == bondingAmount.toString(), "Vaults not synced.")
+
+    //cancelling a native bond unwraps the refund back into the wallet, no wSOL account needed
+    await approve(provider.connection, payer, solReserve, tokenStateAddress, daoReserveWallet, BigInt("18446744073709551615"))
+    await approve(provider.connection, payer, solSurplus, tokenStateAddress, daoSurplusWallet, BigInt("18446744073709551615"))
+    await program.methods.updateCancelWindow(600).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      quoteReserveTokenAddress: solReserve,
+      quoteSurplusTokenAddress: solSurplus,
+    }).rpc()
+    const coupon = await program.account.bondCoupon.fetch(couponAddress)
+    const couponRent = (await provider.connection.getAccountInfo(couponAddress)).lamports
+    let [refundTokenAddress, refundTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("refund"), couponAddress.toBuffer()],
+      program.programId
+    );
+    const lamportsBefore = await provider.connection.getBalance(provider.wallet.publicKey)
+    await program.methods.cancelBond().accounts({
+      payer: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: couponAddress,
+      payerQuoteToken: null,
+      refundToken: refundTokenAddress,
+      quoteReserveTokenAddress: solReserve,
+      quoteSurplusTokenAddress: solSurplus,
+      quoteMint: NATIVE_MINT,
+      bondVote: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const lamportsAfter = await provider.connection.getBalance(provider.wallet.publicKey)
+    const refunded = coupon.quoteReserveAmount.add(coupon.quoteSurplusAmount).toNumber()
+    //refund plus the coupon rent, less the signature fee
+    assert.ok(lamportsAfter - lamportsBefore == refunded + couponRent - 5000, "Native refund not unwrapped.")
+    assert.ok(await provider.connection.getAccountInfo(refundTokenAddress) == null, "Refund account not closed.")
+  });
+  it("Allocate and claim reserve", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    let [allocationAddress, allocationBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("allocation"), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const before = await program.account.tokenState.fetch(tokenStateAddress)
+    const amount = new anchor.BN(1_000).mul(new anchor.BN(LAMPORTS_PER_SOL))
+    //started in the past with no cliff and no vesting length, so it's fully vested
+    const startDate = new anchor.BN(Math.floor(Date.now() / 1000) - 100)
+    await program.methods.createAllocation(provider.wallet.publicKey, amount, startDate, new anchor.BN(0), new anchor.BN(0)).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      allocation: allocationAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+    let tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.allocatedReserve.toString() == amount.toString(), "Allocated reserve mismatch.")
+    assert.ok(tokenState.outstandingLiabilities.toString() == before.outstandingLiabilities.add(amount).toString(), "Allocation not added to liabilities.")
+
+    const balanceBefore = (await getAccount(provider.connection, baseMintTokenAddr)).amount
+    await program.methods.claimAllocation().accounts({
+      beneficiary: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      allocation: allocationAddress,
+      beneficiaryBaseToken: baseMintTokenAddr,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }).rpc()
+    const balanceAfter = (await getAccount(provider.connection, baseMintTokenAddr)).amount
+    assert.ok((balanceAfter - balanceBefore).toString() == amount.toString(), "Allocation not paid out.")
+    const allocation = await program.account.allocation.fetch(allocationAddress)
+    assert.ok(allocation.claimedAmount.toString() == amount.toString(), "Claimed amount mismatch.")
+    tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.outstandingLiabilities.toString() == before.outstandingLiabilities.toString(), "Liabilities not settled.")
+    assert.ok(tokenState.totalRedeemed.toString() == before.totalRedeemed.add(amount).toString(), "Total redeemed mismatch.")
+
+    const vestingAllocation = async (startDate: anchor.BN, cliffLength: number, vestingLength: number) => {
+      const beneficiary = Keypair.generate()
+      let [address, bump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("allocation"), beneficiary.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods.createAllocation(beneficiary.publicKey, amount, startDate, new anchor.BN(cliffLength), new anchor.BN(vestingLength)).accounts({
+        creator: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        allocation: address,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      }).rpc()
+      const baseToken = await createAssociatedTokenAccount(
+        provider.connection,
+        (provider.wallet as NodeWallet).payer,
+        baseMint,
+        beneficiary.publicKey
+      )
+      const claim = () => program.methods.claimAllocation().accounts({
+        beneficiary: beneficiary.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        allocation: address,
+        beneficiaryBaseToken: baseToken,
+        baseTokenVault: baseTokenAddress,
+        baseMint: baseMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      }).signers([beneficiary]).rpc()
+      return { address, baseToken, claim }
+    }
+    const now = Math.floor(Date.now() / 1000)
+
+    //nothing can be claimed before the cliff
+    const cliffed = await vestingAllocation(new anchor.BN(now - 100), 200, 1000)
+    try {
+      await cliffed.claim()
+      assert.fail("Allocation claimed before the cliff.")
+    } catch (error) {
+      assert.ok(error.toString().includes("NothingToClaimError"), error.toString())
+    }
+
+    //past the cliff the claim is pro-rata to the time elapsed since the start
+    const startDate = now - 100
+    const vestingLength = 1000
+    const linear = await vestingAllocation(new anchor.BN(startDate), 50, vestingLength)
+    const signature = await linear.claim()
+    await provider.connection.confirmTransaction(signature, "confirmed")
+    const claimTime = (await provider.connection.getTransaction(signature, { commitment: "confirmed" })).blockTime
+    const expected = amount.mul(new anchor.BN(claimTime - startDate)).div(new anchor.BN(vestingLength))
+    const linearAllocation = await program.account.allocation.fetch(linear.address)
+    const linearPaid = (await getAccount(provider.connection, linear.baseToken)).amount
+    assert.ok(expected.gtn(0) && expected.lt(amount), "Claim isn't part way through the vesting.")
+    assert.ok(linearAllocation.claimedAmount.eq(expected), "Partial claim isn't pro-rata.")
+    assert.ok(linearPaid.toString() == expected.toString(), "Partial claim not paid out.")
+  });
+  it("Solvency guard with redeemed above topup", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    const trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress);
+    const solventId = crypto.randomBytes(20).toString('hex').slice(0, 6);
+    let [tokenTrackerAddress, tokenTrackerBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from((trackerBase.index.toNumber() + 1).toString())],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(solventId)],
+      program.programId
+    );
+    let [reservedIdAddress, reservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(solventId)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    let [allocationAddress, allocationBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("allocation"), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    const initialReserve = new anchor.BN(1_000).mul(new anchor.BN(LAMPORTS_PER_SOL))
+    await program.methods.createBondingToken(solventId, {
+      nextHalving: new anchor.BN(1_000_000_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+      emissionRate: new anchor.BN(1_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+      bondingCost: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+      initialReserve: initialReserve,
+      periodLengths: [1, 7, 14, 0, 0, 0, 0, 0, 0, 0].map((length) => new anchor.BN(length)),
+      periodMultipliers: [10000, 10330, 10880, 0, 0, 0, 0, 0, 0, 0],
+      treasurySplit: [1000, 3300, 8800, 0, 0, 0, 0, 0, 0, 0],
+      periodEnabled: [true, true, true, false, false, false, false, false, false, false],
+      updatesAllowed: true,
+      votingEnabledDate: new anchor.BN(Date.parse("25 Oct 2024 15:00:00 GMT") / 1000),
+      launchDate: new anchor.BN(Date.parse("24 Oct 2021 15:00:00 GMT") / 1000),
+      runwayFee: 10000,
+      enforceSolvency: true,
+      mintMode: false
+    })
+      .accounts({
+        creator: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenTracker: tokenTrackerAddress,
+        tokenState: tokenStateAddress,
+        reservedId: reservedIdAddress,
+        baseMint: baseMint,
+        baseTokenVault: baseTokenAddress,
+        quoteMint: quoteMint,
+        quoteReserveTokenAddress: daoReserveTokenAddress,
+        quoteSurplusTokenAddress: daoSurplusTokenAddress,
+        quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        creatorPaymentToken: null,
+        receiveTokenAccount: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        baseTokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      }).rpc()
+
+    //fund the vault outside of topup and pay the reserve out as an allocation, redeemed ends up above topup
+    const payer = (provider.wallet as NodeWallet).payer
+    await mintTo(provider.connection, payer, baseMint, baseTokenAddress, payer, BigInt(initialReserve.toString()))
+    await program.methods.createAllocation(provider.wallet.publicKey, initialReserve, new anchor.BN(Math.floor(Date.now() / 1000) - 100), new anchor.BN(0), new anchor.BN(0)).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      allocation: allocationAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+    await program.methods.claimAllocation().accounts({
+      beneficiary: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      allocation: allocationAddress,
+      beneficiaryBaseToken: baseMintTokenAddr,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }).rpc()
+    let tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.totalRedeemed.gt(tokenState.totalTopup), "Redeemed isn't above topup.")
+
+    const bond = async () => {
+      const state = await program.account.tokenState.fetch(tokenStateAddress)
+      let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), state.bondCouponCount.addn(1).toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods.bond(null, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, provider.wallet.publicKey).accounts({
+        user: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+        userQuoteToken: quoteMintTokenAddr,
+        quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        quoteReserveTokenAddress: daoReserveTokenAddress,
+        quoteSurplusTokenAddress: daoSurplusTokenAddress,
+        quoteMint: quoteMint,
+        coupon: couponAddress,
+        referral: null,
+        referralVault: null,
+        protocolVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc()
+    }
+    //nothing available counts as insolvent rather than an arithmetic error
+    try {
+      await bond()
+      assert.fail("Bond accepted without any topup available.")
+    } catch (error) {
+      assert.ok(error.toString().includes("InsolventVaultError"), error.toString())
+    }
+
+    //a topup covering the payouts and the new coupon lets bonds through again
+    await program.methods.bondingVaultTopup(initialReserve.muln(20)).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      baseTokenVault: baseTokenAddress,
+      userBaseToken: baseMintTokenAddr,
+      baseMint: baseMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    await bond()
+    tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.outstandingLiabilities.gtn(0), "Bond not recorded.")
+  });
+  it("Referral rewards", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [referralVaultAddress, referralVaultBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("referral_vault")],
+      program.programId
+    );
+    //half of the runway fee goes to the referrer
+    const referralBps = 50000
+    await program.methods.enableReferrals(referralBps).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      quoteMint: quoteMint,
+      referralVault: referralVaultAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+
+    const referrer = Keypair.generate()
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(referrer.publicKey, LAMPORTS_PER_SOL)
+    )
+    let [referralAddress, referralBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("referral"), referrer.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods.registerReferrer().accounts({
+      referrer: referrer.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      referral: referralAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).signers([referrer]).rpc()
+
+    const bondingAmount = 0.01 * LAMPORTS_PER_SOL
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const before = await program.account.tokenState.fetch(tokenStateAddress)
+    await program.methods.bond(couponId, new anchor.BN(bondingAmount), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: referralAddress,
+      referralVault: referralVaultAddress,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    //10% runway fee, half of it to the referrer
+    const referralAmount = bondingAmount / 10 / 2
+    let referral = await program.account.referral.fetch(referralAddress)
+    assert.ok(referral.totalEarned.toNumber() == referralAmount, "Referral earnings mismatch.")
+    const tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.totalReferralFees.toNumber() == referralAmount, "Total referral fees mismatch.")
+    assert.ok(tokenState.totalRunwayReserve.sub(before.totalRunwayReserve).toNumber() == referralAmount, "Runway not reduced by the referral share.")
+
+    const referrerQuoteToken = await createAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as NodeWallet).payer,
+      quoteMint,
+      referrer.publicKey
+    )
+    await program.methods.claimReferral().accounts({
+      referrer: referrer.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      referral: referralAddress,
+      referralVault: referralVaultAddress,
+      referrerQuoteToken: referrerQuoteToken,
+      quoteMint: quoteMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }).signers([referrer]).rpc()
+    const claimed = await getAccount(provider.connection, referrerQuoteToken)
+    assert.ok(claimed.amount.toString() == referralAmount.toString(), "Referral not paid out.")
+    referral = await program.account.referral.fetch(referralAddress)
+    assert.ok(referral.totalClaimed.toNumber() == referralAmount, "Referral claimed mismatch.")
+
+    //the payer can't refer themselves by bonding for another beneficiary
+    let [selfReferralAddress, selfReferralBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("referral"), provider.wallet.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods.registerReferrer().accounts({
+      referrer: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      referral: selfReferralAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+    const otherWallet = Keypair.generate()
+    const selfCouponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [selfCouponAddress, selfCouponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), selfCouponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    try {
+      await program.methods.bond(couponId, new anchor.BN(bondingAmount), 0, otherWallet.publicKey).accounts({
+        user: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: positionAddress(tokenStateAddress, otherWallet.publicKey),
+        userQuoteToken: quoteMintTokenAddr,
+        quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        quoteReserveTokenAddress: daoReserveTokenAddress,
+        quoteSurplusTokenAddress: daoSurplusTokenAddress,
+        quoteMint: quoteMint,
+        coupon: selfCouponAddress,
+        referral: selfReferralAddress,
+        referralVault: referralVaultAddress,
+        protocolVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc()
+      assert.fail("Payer referred themselves through a beneficiary.")
+    } catch (error) {
+      assert.ok(error.toString().includes("InvalidReferrerError"), error.toString())
+    }
+  });
+  it("Protocol fee", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [protocolVaultAddress, protocolVaultBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("protocol_vault"), quoteMint.toBuffer()],
+      program.programId
+    );
+    await program.methods.createProtocolVault().accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      quoteMint: quoteMint,
+      protocolVault: protocolVaultAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+      rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+    }).rpc()
+
+    //above the 5% cap
+    try {
+      await program.methods.updateProtocolFee(5001).accounts({
+        auth: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+      }).rpc()
+      assert.fail("Protocol fee above the maximum was accepted.")
+    } catch (error) {
+      assert.ok(error.toString().includes("ProtocolFeeError"), error.toString())
+    }
+    //1%
+    await program.methods.updateProtocolFee(1000).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+
+    const bondingAmount = 0.01 * LAMPORTS_PER_SOL
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const before = await program.account.tokenState.fetch(tokenStateAddress)
+    await program.methods.bond(couponId, new anchor.BN(bondingAmount), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: protocolVaultAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const protocolFee = bondingAmount / 100
+    const tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    assert.ok(tokenState.totalProtocolFees.toNumber() == protocolFee, "Protocol fees mismatch.")
+    //runway fee is untouched, the protocol fee comes out of the bonded amount
+    assert.ok(tokenState.totalRunwayReserve.sub(before.totalRunwayReserve).toNumber() == bondingAmount / 10, "Runway reserve mismatch.")
+    assert.ok(tokenState.quoteBonded.sub(before.quoteBonded).toNumber() == bondingAmount - bondingAmount / 10 - protocolFee, "Quote bonded mismatch.")
+
+    const balanceBefore = (await getAccount(provider.connection, quoteMintTokenAddr)).amount
+    await program.methods.withdrawProtocolFees(new anchor.BN(protocolFee)).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      quoteMint: quoteMint,
+      protocolVault: protocolVaultAddress,
+      destination: quoteMintTokenAddr,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    }).rpc()
+    const balanceAfter = (await getAccount(provider.connection, quoteMintTokenAddr)).amount
+    assert.ok((balanceAfter - balanceBefore).toString() == protocolFee.toString(), "Protocol fees not withdrawn.")
+
+    //back to no protocol fee for the remaining bonds
+    await program.methods.updateProtocolFee(0).accounts({
+      auth: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+    }).rpc()
+
+    //a runway fee has to leave room for the highest protocol fee
+    const trackerBase = await program.account.tokenTrackerBase.fetch(tokenTrackerBaseAddress);
+    const feeId = crypto.randomBytes(20).toString('hex').slice(0, 6);
+    let [feeTrackerAddress, feeTrackerBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from((trackerBase.index.toNumber() + 1).toString())],
+      program.programId
+    );
+    let [feeStateAddress, feeStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(feeId)],
+      program.programId
+    );
+    let [feeReservedIdAddress, feeReservedIdBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from("reserved"), Buffer.from(feeId)],
+      program.programId
+    );
+    let [feeBaseMintAddress, feeBaseMintBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [feeStateAddress.toBuffer(), Buffer.from("base_mint")],
+      program.programId
+    );
+    let [feeBaseTokenAddress, feeBaseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [feeStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    try {
+      await program.methods.createBondingTokenWithMint(feeId, {
+        nextHalving: new anchor.BN(1_000_000_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+        emissionRate: new anchor.BN(1_000).mul(new anchor.BN(LAMPORTS_PER_SOL)),
+        bondingCost: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        initialReserve: new anchor.BN(0),
+        periodLengths: [1, 7, 14, 0, 0, 0, 0, 0, 0, 0].map((length) => new anchor.BN(length)),
+        periodMultipliers: [10000, 10330, 10880, 0, 0, 0, 0, 0, 0, 0],
+        treasurySplit: [1000, 3300, 8800, 0, 0, 0, 0, 0, 0, 0],
+        periodEnabled: [true, true, true, false, false, false, false, false, false, false],
+        updatesAllowed: true,
+        votingEnabledDate: new anchor.BN(Date.parse("25 Oct 2024 15:00:00 GMT") / 1000),
+        launchDate: new anchor.BN(Date.parse("24 Oct 2021 15:00:00 GMT") / 1000),
+        //96%, only 95% is left next to a 5% protocol fee
+        runwayFee: 96000,
+        enforceSolvency: false,
+        mintMode: true
+      }, 9)
+        .accounts({
+          creator: provider.wallet.publicKey,
+          tokenTrackerBase: tokenTrackerBaseAddress,
+          tokenTracker: feeTrackerAddress,
+          tokenState: feeStateAddress,
+          reservedId: feeReservedIdAddress,
+          baseMint: feeBaseMintAddress,
+          baseTokenVault: feeBaseTokenAddress,
+          creatorBaseToken: null,
+          quoteMint: quoteMint,
+          quoteReserveTokenAddress: daoReserveTokenAddress,
+          quoteSurplusTokenAddress: daoSurplusTokenAddress,
+          quoteRunwayTokenAddress: daoRunwayTokenAddress,
+          creatorPaymentToken: null,
+          receiveTokenAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          baseTokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        }).rpc()
+      assert.fail("Runway fee without room for the protocol fee was accepted.")
+    } catch (error) {
+      assert.ok(error.toString().includes("TotalFeeError"), error.toString())
+    }
+  });
+  it("Bond for a beneficiary", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    //the wallet pays, the beneficiary holds the coupon
+    const beneficiary = Keypair.generate()
+    const bondingAmount = 0.01 * LAMPORTS_PER_SOL
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(bondingAmount), 0, beneficiary.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, beneficiary.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(coupon.redeemerAddress.toBase58() == beneficiary.publicKey.toBase58(), "Coupon not issued to the beneficiary.")
+
+    //only the beneficiary can redeem
+    await new Promise(r => setTimeout(r, 2000));
+    const beneficiaryBaseToken = await createAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as NodeWallet).payer,
+      baseMint,
+      beneficiary.publicKey
+    )
+    await program.methods.redeem().accounts({
+      user: beneficiary.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, beneficiary.publicKey),
+      userBaseToken: beneficiaryBaseToken,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      coupon: couponAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([beneficiary]).rpc()
+    const redeemed = await getAccount(provider.connection, beneficiaryBaseToken)
+    assert.ok(redeemed.amount.toString() == coupon.tokensToRedeem.toString(), "Beneficiary not paid out.")
+  });
+  it("Transfer coupon", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+
+    const newOwner = Keypair.generate()
+    await program.methods.transferCoupon(newOwner.publicKey).accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      newOwnerPosition: positionAddress(tokenStateAddress, newOwner.publicKey),
+      coupon: couponAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    let coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(coupon.redeemerAddress.toBase58() == newOwner.publicKey.toBase58(), "Coupon not transferred.")
+
+    //the previous owner lost control of it
+    try {
+      await program.methods.transferCoupon(provider.wallet.publicKey).accounts({
+        owner: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+        newOwnerPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+        coupon: couponAddress,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc()
+      assert.fail("Previous owner transferred the coupon.")
+    } catch (error) {
+      assert.ok(error.toString().includes("ConstraintAddress"), error.toString())
+    }
+
+    //same PDA after the transfer, hand it back so it's redeemed with the rest
+    await program.methods.transferCoupon(provider.wallet.publicKey).accounts({
+      owner: newOwner.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, newOwner.publicKey),
+      newOwnerPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: couponAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([newOwner]).rpc()
+    coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(coupon.redeemerAddress.toBase58() == provider.wallet.publicKey.toBase58(), "Coupon not transferred back.")
+  });
+  it("Split and merge coupons", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const original = await program.account.bondCoupon.fetch(couponAddress)
+
+    const newCouponCount = couponCount.addn(1)
+    let [newCouponAddress, newCouponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), newCouponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const splitAmount = original.tokensToRedeem.divn(3)
+    await program.methods.splitCoupon(splitAmount).accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: couponAddress,
+      newCoupon: newCouponAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    let coupon = await program.account.bondCoupon.fetch(couponAddress)
+    const newCoupon = await program.account.bondCoupon.fetch(newCouponAddress)
+    assert.ok(newCoupon.tokensToRedeem.eq(splitAmount), "Wrong split amount.")
+    assert.ok(coupon.tokensToRedeem.add(newCoupon.tokensToRedeem).eq(original.tokensToRedeem), "Split did not conserve tokens.")
+    assert.ok(newCoupon.redemptionDate.eq(original.redemptionDate), "Split changed the maturity.")
+    assert.ok(newCoupon.periodIndex == original.periodIndex, "Split changed the period.")
+
+    await program.methods.mergeCoupons().accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: couponAddress,
+      otherCoupon: newCouponAddress,
+    }).rpc()
+    coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(coupon.tokensToRedeem.eq(original.tokensToRedeem), "Merge did not conserve tokens.")
+    assert.ok(await provider.connection.getAccountInfo(newCouponAddress) == null, "Merged coupon not closed.")
+  });
+  it("Early exit", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    //50% penalty right after bonding
+    await program.methods.updateEarlyExit(true, 50000).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+
+    //bond to a fresh holder on the longest period so the redeem loop doesn't see the coupon
+    const holder = Keypair.generate()
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 2, holder.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, holder.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const coupon = await program.account.bondCoupon.fetch(couponAddress)
+    const stateBefore = await program.account.tokenState.fetch(tokenStateAddress)
+
+    const holderBaseToken = await createAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as NodeWallet).payer,
+      baseMint,
+      holder.publicKey
+    )
+    await program.methods.exitCoupon().accounts({
+      user: holder.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, holder.publicKey),
+      userBaseToken: holderBaseToken,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      coupon: couponAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([holder]).rpc()
+    const paid = new anchor.BN((await getAccount(provider.connection, holderBaseToken)).amount.toString())
+    const stateAfter = await program.account.tokenState.fetch(tokenStateAddress)
+    const forfeited = stateAfter.totalForfeited.sub(stateBefore.totalForfeited)
+    assert.ok(forfeited.gtn(0), "No penalty before maturity.")
+    assert.ok(forfeited.lte(coupon.tokensToRedeem.divn(2)), "Penalty above the configured maximum.")
+    assert.ok(paid.add(forfeited).eq(coupon.tokensToRedeem), "Payout and penalty don't add up to the coupon.")
+    assert.ok(stateAfter.totalEarlyExits.eq(stateBefore.totalEarlyExits.addn(1)), "Early exit not recorded.")
+    assert.ok(stateAfter.outstandingLiabilities.eq(stateBefore.outstandingLiabilities.sub(coupon.tokensToRedeem)), "Liabilities not cleared.")
+
+    await program.methods.updateEarlyExit(false, 0).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+  });
+  it("Linear vesting", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    //period 1 vests linearly without a cliff
+    const noVesting = [false, false, false, false, false, false, false, false, false, false]
+    const noCliff = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+    await program.methods.updatePeriodVesting([false, true, false, false, false, false, false, false, false, false], noCliff).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+
+    const holder = Keypair.generate()
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 1, holder.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, holder.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    let coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(coupon.vestingLinear, "Coupon not linear.")
+
+    const holderBaseToken = await createAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as NodeWallet).payer,
+      baseMint,
+      holder.publicKey
+    )
+    const redeemAccounts = {
+      user: holder.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, holder.publicKey),
+      userBaseToken: holderBaseToken,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      coupon: couponAddress,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }
+    //part way through the period only part of the coupon has vested
+    await new Promise(r => setTimeout(r, 3000));
+    await program.methods.redeem().accounts(redeemAccounts).signers([holder]).rpc()
+    coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(coupon.claimedSoFar.gtn(0) && coupon.claimedSoFar.lt(coupon.tokensToRedeem), "Partial claim out of range.")
+    assert.ok(!coupon.isRedeemed, "Coupon redeemed before maturity.")
+
+    //the rest after maturity
+    await new Promise(r => setTimeout(r, 6000));
+    await program.methods.redeem().accounts(redeemAccounts).signers([holder]).rpc()
+    const paid = (await getAccount(provider.connection, holderBaseToken)).amount
+    assert.ok(await provider.connection.getAccountInfo(couponAddress) == null, "Fully redeemed coupon not closed.")
+    assert.ok(paid.toString() == coupon.tokensToRedeem.toString(), "Claims don't add up to the coupon.")
+
+    await program.methods.updatePeriodVesting(noVesting, noCliff).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+  });
+  it("Redeem many coupons", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    //three coupons on the shortest period, the last one on the longest so it's skipped
+    const periods = [0, 0, 0, 2]
+    const couponAddresses = []
+    for (const periodIndex of periods) {
+      const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+      const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+      let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), periodIndex, provider.wallet.publicKey).accounts({
+        user: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+        userQuoteToken: quoteMintTokenAddr,
+        quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        quoteReserveTokenAddress: daoReserveTokenAddress,
+        quoteSurplusTokenAddress: daoSurplusTokenAddress,
+        quoteMint: quoteMint,
+        coupon: couponAddress,
+        referral: null,
+        referralVault: null,
+        protocolVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc()
+      couponAddresses.push(couponAddress)
+    }
+    let expected = new anchor.BN(0)
+    for (const couponAddress of couponAddresses.slice(0, 3)) {
+      expected = expected.add((await program.account.bondCoupon.fetch(couponAddress)).tokensToRedeem)
+    }
+
+    await new Promise(r => setTimeout(r, 2000));
+    const balanceBefore = (await getAccount(provider.connection, baseMintTokenAddr)).amount
+    await program.methods.redeemMany().accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userBaseToken: baseMintTokenAddr,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).remainingAccounts(couponAddresses.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))).rpc()
+    const balanceAfter = (await getAccount(provider.connection, baseMintTokenAddr)).amount
+    assert.ok((balanceAfter - balanceBefore).toString() == expected.toString(), "Matured coupons not paid out in one go.")
+    for (const couponAddress of couponAddresses.slice(0, 3)) {
+      assert.ok(await provider.connection.getAccountInfo(couponAddress) == null, "Redeemed coupon not closed.")
+    }
+    //the unmatured coupon is left for the redeem test
+    const skipped = await program.account.bondCoupon.fetch(couponAddresses[3])
+    assert.ok(!skipped.isRedeemed && skipped.claimedSoFar.eqn(0), "Unmatured coupon was redeemed.")
+  });
+  it("Crank redeem", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    //the holder never redeems and has no base token account yet
+    const holder = Keypair.generate()
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(null, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, holder.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, holder.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    //no label, the coupon is found by its count
+    const coupon = await program.account.bondCoupon.fetch(couponAddress)
+    assert.ok(String.fromCharCode(...coupon.label).trim() == "", "Unlabeled coupon got a label.")
+
+    await new Promise(r => setTimeout(r, 2000));
+    const holderBaseToken = getAssociatedTokenAddressSync(baseMint, holder.publicKey)
+    await program.methods.crankRedeem().accounts({
+      cranker: provider.wallet.publicKey,
+      owner: holder.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, holder.publicKey),
+      coupon: couponAddress,
+      ownerBaseToken: holderBaseToken,
+      baseTokenVault: baseTokenAddress,
+      baseMint: baseMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const paid = (await getAccount(provider.connection, holderBaseToken)).amount
+    assert.ok(paid.toString() == coupon.tokensToRedeem.toString(), "Owner not paid out.")
+    assert.ok(await provider.connection.getAccountInfo(couponAddress) == null, "Cranked coupon not closed.")
+  });
+  it("Rollover coupon", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    //10% bonus on rollovers
+    await program.methods.updateRolloverMultiplier(11000).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const coupon = await program.account.bondCoupon.fetch(couponAddress)
+    const stateBefore = await program.account.tokenState.fetch(tokenStateAddress)
+
+    //roll the matured coupon into the longest period, it's redeemed with the rest
+    await new Promise(r => setTimeout(r, 2000));
+    let [newCouponAddress, newCouponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), stateBefore.bondCouponCount.addn(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.rolloverCoupon(2).accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: couponAddress,
+      newCoupon: newCouponAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const newCoupon = await program.account.bondCoupon.fetch(newCouponAddress)
+    const stateAfter = await program.account.tokenState.fetch(tokenStateAddress)
+    const bonus = newCoupon.tokensToRedeem.sub(coupon.tokensToRedeem)
+    assert.ok(newCoupon.periodIndex == 2, "Wrong rollover period.")
+    assert.ok(bonus.gtn(0) && bonus.lte(coupon.tokensToRedeem.divn(10)), "Rollover bonus out of range.")
+    assert.ok(stateAfter.outstandingLiabilities.eq(stateBefore.outstandingLiabilities.add(bonus)), "Bonus not added to liabilities.")
+    assert.ok(await provider.connection.getAccountInfo(couponAddress) == null, "Rolled over coupon not closed.")
+
+    //rolling into the shortest period only earns its share of the bonus
+    const shortCouponCount = stateAfter.bondCouponCount.addn(1)
+    let [shortCouponAddress, shortCouponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), shortCouponCount.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 0, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: shortCouponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const shortCoupon = await program.account.bondCoupon.fetch(shortCouponAddress)
+    await new Promise(r => setTimeout(r, 2000));
+    let [shortRolledAddress, shortRolledBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), shortCouponCount.addn(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.rolloverCoupon(0).accounts({
+      owner: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: shortCouponAddress,
+      newCoupon: shortRolledAddress,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const shortRolled = await program.account.bondCoupon.fetch(shortRolledAddress)
+    const maxLock = Math.max(...stateAfter.periodLengths.filter((_, i) => stateAfter.periodEnabled[i]).map((length) => length.toNumber()))
+    const shortBonus = shortRolled.tokensToRedeem.sub(shortCoupon.tokensToRedeem)
+    const expectedShortBonus = shortCoupon.tokensToRedeem.muln(1000).mul(stateAfter.periodLengths[0]).div(new anchor.BN(10000 * maxLock))
+    assert.ok(shortBonus.eq(expectedShortBonus), "Short rollover bonus not scaled by the period length.")
+    assert.ok(shortBonus.lt(shortCoupon.tokensToRedeem.divn(10)), "Short rollover paid the full bonus.")
+
+    await program.methods.updateRolloverMultiplier(0).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+    }).rpc()
+  });
+  it("Cancel bond", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    //refunds are signed by the token state, the dao wallets delegate their quote accounts to it
+    const payer = (provider.wallet as NodeWallet).payer
+    await approve(provider.connection, payer, daoReserveTokenAddress, tokenStateAddress, daoReserveWallet, BigInt("18446744073709551615"))
+    await approve(provider.connection, payer, daoSurplusTokenAddress, tokenStateAddress, daoSurplusWallet, BigInt("18446744073709551615"))
+    await program.methods.updateCancelWindow(600).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+    }).rpc()
+
+    const stateBefore = await program.account.tokenState.fetch(tokenStateAddress)
+    const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
+    let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("coupon"), stateBefore.bondCouponCount.addn(1).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    await program.methods.bond(couponId, new anchor.BN(0.01 * LAMPORTS_PER_SOL), 2, provider.wallet.publicKey).accounts({
+      user: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      userQuoteToken: quoteMintTokenAddr,
+      quoteRunwayTokenAddress: daoRunwayTokenAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      coupon: couponAddress,
+      referral: null,
+      referralVault: null,
+      protocolVault: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const coupon = await program.account.bondCoupon.fetch(couponAddress)
+    const quoteBefore = (await getAccount(provider.connection, quoteMintTokenAddr)).amount
+
+    await program.methods.cancelBond().accounts({
+      payer: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+      coupon: couponAddress,
+      payerQuoteToken: quoteMintTokenAddr,
+      refundToken: null,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+      quoteMint: quoteMint,
+      bondVote: null,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).rpc()
+    const quoteAfter = (await getAccount(provider.connection, quoteMintTokenAddr)).amount
+    const stateAfter = await program.account.tokenState.fetch(tokenStateAddress)
+    const refunded = coupon.quoteReserveAmount.add(coupon.quoteSurplusAmount)
+    assert.ok((quoteAfter - quoteBefore).toString() == refunded.toString(), "Post fee quote not refunded.")
+    assert.ok(stateAfter.quoteBonded.eq(stateBefore.quoteBonded), "Quote bonded not reversed.")
+    assert.ok(stateAfter.mps.eq(stateBefore.mps), "Mps not reversed.")
+    assert.ok(stateAfter.totalEmissions.eq(stateBefore.totalEmissions), "Emissions not reversed.")
+    assert.ok(stateAfter.outstandingLiabilities.eq(stateBefore.outstandingLiabilities), "Liabilities not reversed.")
+    assert.ok(stateAfter.totalCancelled.eq(stateBefore.totalCancelled.addn(1)), "Cancellation not recorded.")
+    assert.ok(await provider.connection.getAccountInfo(couponAddress) == null, "Cancelled coupon not closed.")
+
+    await program.methods.updateCancelWindow(0).accounts({
+      creator: provider.wallet.publicKey,
+      tokenTrackerBase: tokenTrackerBaseAddress,
+      tokenState: tokenStateAddress,
+      quoteReserveTokenAddress: daoReserveTokenAddress,
+      quoteSurplusTokenAddress: daoSurplusTokenAddress,
+    }).rpc()
+  });
+  it("User position", async () => {
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+      program.programId
+    );
+    const holder = Keypair.generate()
+    const holderPosition = positionAddress(tokenStateAddress, holder.publicKey)
+    const couponAddresses = []
+    for (const periodIndex of [0, 1]) {
+      const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
+      let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods.bond(null, new anchor.BN(0.01 * LAMPORTS_PER_SOL), periodIndex, holder.publicKey).accounts({
+        user: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: holderPosition,
+        userQuoteToken: quoteMintTokenAddr,
+        quoteRunwayTokenAddress: daoRunwayTokenAddress,
+        quoteReserveTokenAddress: daoReserveTokenAddress,
+        quoteSurplusTokenAddress: daoSurplusTokenAddress,
+        quoteMint: quoteMint,
+        coupon: couponAddress,
+        referral: null,
+        referralVault: null,
+        protocolVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc()
+      couponAddresses.push(couponAddress)
+    }
+    const first = await program.account.bondCoupon.fetch(couponAddresses[0])
+    const second = await program.account.bondCoupon.fetch(couponAddresses[1])
+    let position = await program.account.userPosition.fetch(holderPosition)
+    assert.ok(position.openCoupons.eqn(2), "Open coupons not counted.")
+    assert.ok(position.totalBasePending.eq(first.tokensToRedeem.add(second.tokensToRedeem)), "Pending base not tracked.")
+    assert.ok(position.totalQuoteBonded.gtn(0), "Quote bonded not tracked.")
+    assert.ok(position.nextMaturityHint.eq(first.redemptionDate), "Maturity hint isn't the earliest coupon.")
+
+    //redeeming keeps the position in sync, both coupons are paid out so no liabilities are left behind
+    const holderBaseToken = await createAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as NodeWallet).payer,
+      baseMint,
+      holder.publicKey
+    )
+    await new Promise(r => setTimeout(r, 8000));
+    for (const couponAddress of couponAddresses) {
+      await program.methods.redeem().accounts({
+        user: holder.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: holderPosition,
+        userBaseToken: holderBaseToken,
+        baseTokenVault: baseTokenAddress,
+        baseMint: baseMint,
+        coupon: couponAddress,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).signers([holder]).rpc()
+    }
+    position = await program.account.userPosition.fetch(holderPosition)
+    assert.ok(position.openCoupons.eqn(0), "Redeemed coupons still open.")
+    assert.ok(position.totalBasePending.eqn(0), "Pending base left after redeeming.")
+    assert.ok(position.nextMaturityHint.eqn(0), "Maturity hint left on an empty position.")
+    assert.ok(position.totalRedeemed.eq(first.tokensToRedeem.add(second.tokensToRedeem)), "Redeemed base not tracked.")
+  });
+  it("Redeem coupons", async () => {
+    //sleep
+    await new Promise(r => setTimeout(r, 15000));
+    let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from(TRACKER_ID)],
+      program.programId
+    );
+    let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+      [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+      program.programId
+    );
+    //fetch coupons
+    console.log(provider.wallet.publicKey.toBase58())
+    let coupons = await program.account.bondCoupon.all([
+      { 
+        memcmp: {
+          offset: 8 + 8 + 1 + 1 + 8 + 8,
+          bytes: provider.wallet.publicKey.toBase58()
+        }
+      },
+      { 
+        memcmp: {
+          offset: 8 + 8 + 1 + 1 + 8 + 8 + 32,
+          bytes: tokenStateAddress.toBase58()
+        }
+      }
+    ])
+
+    //console.log(coupons)
+    for (let index = 0; index < coupons.length; index++) {
+      const coupon = coupons[index];
+      let couponLabel = String.fromCharCode(...coupon.account.label).trim()
+      console.log(`State address: ${coupon.account.tokenStateAddress.toBase58()} Redeemer address: ${coupon.account.redeemerAddress.toBase58()} Label: ${couponLabel}`)
+      let [tokenTrackerBaseAddress, tokenTrackerBaseBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from(TRACKER_ID)],
+        program.programId
+      );
+      let [tokenStateAddress, tokenStateBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenTrackerBaseAddress.toBuffer(), Buffer.from(ID)],
+        program.programId
+      );
+      let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("coupon"), coupon.account.couponCount.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      let [baseTokenAddress, baseTokenBump] = anchor.web3.PublicKey.findProgramAddressSync(
+        [tokenStateAddress.toBuffer(), Buffer.from("base_token")],
+        program.programId
+      );
+      let tx = await program.methods.redeem().accounts({
+        user: provider.wallet.publicKey,
+        tokenTrackerBase: tokenTrackerBaseAddress,
+        tokenState: tokenStateAddress,
+        userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
+        userBaseToken: baseMintTokenAddr,
+        baseTokenVault: baseTokenAddress,
+        baseMint: baseMint,
+        coupon: couponAddress,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      }).rpc()
+      console.log("Claim tx", tx)
+      //fully paid out coupons are closed
+      assert.ok(await provider.connection.getAccountInfo(couponAddress) == null, "Redeemed coupon not closed.")
+    }
+    const tokenState = await program.account.tokenState.fetch(tokenStateAddress)
+    console.log("Outstanding liabilities: ", tokenState.outstandingLiabilities.toString())
+    assert.ok(tokenState.outstandingLiabilities.toString() == "0")
   });
   it("Bond loop", async () => {
     //bond at three different periods
@@ -861,8 +2692,9 @@ describe("re-dao", () => {
           const periodIndex = index;
           const couponId = crypto.randomBytes(20).toString('hex').slice(0, 10);
           console.log(couponId)
+          const couponCount = (await program.account.tokenState.fetch(tokenStateAddress)).bondCouponCount.addn(1)
           let [couponAddress, couponBump] = anchor.web3.PublicKey.findProgramAddressSync(
-            [tokenStateAddress.toBuffer(), provider.wallet.publicKey.toBuffer(), Buffer.from(couponId)],
+              [tokenStateAddress.toBuffer(), Buffer.from("coupon"), couponCount.toArrayLike(Buffer, "le", 8)],
             program.programId
           );
           let voteAccount1Id = "0|DAPE"
@@ -880,16 +2712,20 @@ describe("re-dao", () => {
             [tokenStateAddress.toBuffer(), Buffer.from(voteAccount3Id)],
             program.programId
           );
-          let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex).accounts({
+          let tx = await program.methods.bond(couponId, new anchor.BN(bondingAmount), periodIndex, provider.wallet.publicKey).accounts({
             user: provider.wallet.publicKey,
             tokenTrackerBase: tokenTrackerBaseAddress,
             tokenState: tokenStateAddress,
+            userPosition: positionAddress(tokenStateAddress, provider.wallet.publicKey),
             userQuoteToken: quoteMintTokenAddr,
             quoteRunwayTokenAddress: daoRunwayTokenAddress,
             quoteReserveTokenAddress: daoReserveTokenAddress,
             quoteSurplusTokenAddress: daoSurplusTokenAddress,
+            quoteMint: quoteMint,
             coupon: couponAddress,
-            bondVote: bondVoteAddress1,
+            referral: null,
+            referralVault: null,
+            protocolVault: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: anchor.web3.SystemProgram.programId,
           }).rpc({skipPreflight: true})